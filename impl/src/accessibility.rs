@@ -0,0 +1,359 @@
+//! Accessibility lints over the `rsx!` element tree.
+//!
+//! Stable proc macros can't emit warnings, so lints at the `warn` level expand to
+//! the use of a deprecated item spanned at the offending markup. Lints can be
+//! configured per call site with inner attributes:
+//!
+//! ```ignore
+//! rsx! {
+//!     #![allow(missing_alt)]
+//!     #![deny(heading_order)]
+//!     <img src={"/spacer.gif"} />
+//! }
+//! ```
+
+use crate::element::Element;
+use crate::html_spec;
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::emit_error;
+use quote::quote_spanned;
+use std::collections::HashMap;
+use syn::parse::{ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+/// The name that configures every accessibility lint at once
+const GROUP_NAME: &str = "a11y";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    MissingAlt,
+    MissingLabel,
+    AnchorAsButton,
+    InvalidAria,
+    HeadingOrder,
+    /// Invalid nesting that browsers keep as written, unlike the nesting errors
+    InvalidNesting,
+}
+
+/// The lints configured by the `a11y` group
+const ACCESSIBILITY_LINTS: &[Lint] = &[
+    Lint::MissingAlt,
+    Lint::MissingLabel,
+    Lint::AnchorAsButton,
+    Lint::InvalidAria,
+    Lint::HeadingOrder,
+];
+
+const LINTS: &[Lint] = &[
+    Lint::MissingAlt,
+    Lint::MissingLabel,
+    Lint::AnchorAsButton,
+    Lint::InvalidAria,
+    Lint::HeadingOrder,
+    Lint::InvalidNesting,
+];
+
+impl Lint {
+    fn name(self) -> &'static str {
+        match self {
+            Self::MissingAlt => "missing_alt",
+            Self::MissingLabel => "missing_label",
+            Self::AnchorAsButton => "anchor_as_button",
+            Self::InvalidAria => "invalid_aria",
+            Self::HeadingOrder => "heading_order",
+            Self::InvalidNesting => "invalid_nesting",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        LINTS.iter().copied().find(|lint| lint.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+#[derive(Default)]
+pub struct LintLevels {
+    levels: HashMap<Lint, Level>,
+}
+
+impl LintLevels {
+    /// Parses the `#![allow(..)]`, `#![warn(..)]` and `#![deny(..)]` inner attributes
+    /// at the start of the macro input
+    pub fn parse_inner(input: ParseStream) -> Result<Self> {
+        let mut lint_levels = Self::default();
+
+        for attribute in syn::Attribute::parse_inner(input)? {
+            let level = match attribute.path.get_ident().map(|ident| ident.to_string()) {
+                Some(level) if level == "allow" => Level::Allow,
+                Some(level) if level == "warn" => Level::Warn,
+                Some(level) if level == "deny" => Level::Deny,
+                _ => {
+                    emit_error!(
+                        attribute.path,
+                        "Unknown attribute. Expected `allow`, `warn` or `deny`"
+                    );
+                    continue;
+                }
+            };
+
+            let names = attribute
+                .parse_args_with(Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated)?;
+
+            for name in names {
+                let name_str = name.to_string();
+                if name_str == GROUP_NAME {
+                    for lint in ACCESSIBILITY_LINTS {
+                        lint_levels.levels.insert(*lint, level);
+                    }
+                } else if let Some(lint) = Lint::from_name(&name_str) {
+                    lint_levels.levels.insert(lint, level);
+                } else {
+                    let known_names = std::iter::once(GROUP_NAME)
+                        .chain(LINTS.iter().map(|lint| lint.name()))
+                        .map(|name| format!("`{}`", name))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    emit_error!(
+                        name,
+                        "Unknown lint `{}`. Expected one of {}",
+                        name,
+                        known_names
+                    );
+                }
+            }
+        }
+
+        Ok(lint_levels)
+    }
+
+    fn level(&self, lint: Lint) -> Level {
+        self.levels.get(&lint).copied().unwrap_or(Level::Warn)
+    }
+}
+
+pub struct Diagnostic {
+    pub lint: Lint,
+    pub span: Span,
+    pub message: String,
+}
+
+/// Runs the accessibility lints on `element`, emitting errors for denied lints and
+/// returning the tokens producing the warnings. `diagnostics` are the ones already
+/// found while checking the content model.
+pub fn check(
+    element: &Element,
+    lint_levels: &LintLevels,
+    diagnostics: Vec<Diagnostic>,
+) -> TokenStream {
+    let mut checker = Checker {
+        diagnostics,
+        ..Checker::default()
+    };
+    checker.collect_labels(element);
+    checker.visit(element, false);
+
+    checker
+        .diagnostics
+        .into_iter()
+        .filter_map(|diagnostic| match lint_levels.level(diagnostic.lint) {
+            Level::Allow => None,
+            Level::Deny => {
+                emit_error!(diagnostic.span, "{}", diagnostic.message);
+                None
+            }
+            Level::Warn => Some(warning_tokens(&diagnostic)),
+        })
+        .collect()
+}
+
+fn warning_tokens(diagnostic: &Diagnostic) -> TokenStream {
+    let lint_name = diagnostic.lint.name();
+    let lint = syn::Ident::new(lint_name, diagnostic.span);
+    let note = format!(
+        "{}. Use `#![allow({})]` in the macro to silence this warning",
+        diagnostic.message, lint_name
+    );
+
+    quote_spanned! {diagnostic.span=>
+        {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types)]
+            struct #lint;
+            let _ = #lint;
+        }
+    }
+}
+
+#[derive(Default)]
+struct Checker {
+    diagnostics: Vec<Diagnostic>,
+    /// The `for` values of the `<label>` elements in the macro
+    label_targets: Vec<String>,
+    /// Whether a `<label>` has a `for` value only known at runtime
+    has_dynamic_label_target: bool,
+    previous_heading_level: Option<u32>,
+}
+
+impl Checker {
+    fn report(&mut self, lint: Lint, span: Span, message: String) {
+        self.diagnostics.push(Diagnostic {
+            lint,
+            span,
+            message,
+        });
+    }
+
+    fn collect_labels(&mut self, element: &Element) {
+        if element.tag_name().as_deref() == Some("label") {
+            if let Some(target) = element.attribute("for") {
+                match target.literal_value() {
+                    Some(value) => self.label_targets.push(value),
+                    None => self.has_dynamic_label_target = true,
+                }
+            }
+        }
+
+        for child in element.child_elements() {
+            self.collect_labels(child);
+        }
+    }
+
+    fn visit(&mut self, element: &Element, inside_label: bool) {
+        let inside_label = match element.tag_name() {
+            Some(tag_name) => {
+                self.check_element(element, &tag_name, inside_label);
+                inside_label || tag_name == "label"
+            }
+            None => inside_label,
+        };
+
+        for child in element.child_elements() {
+            self.visit(child, inside_label);
+        }
+    }
+
+    fn check_element(&mut self, element: &Element, tag_name: &str, inside_label: bool) {
+        self.check_aria(element);
+
+        let span = element.name().span();
+        // Spread attributes may provide whatever the lints below look for
+        let spread = element.has_spread_attributes();
+        match tag_name {
+            "img" if !spread && element.attribute("alt").is_none() => self.report(
+                Lint::MissingAlt,
+                span,
+                "<img> is missing an `alt` attribute. Use `alt=\"\"` for decorative images"
+                    .to_string(),
+            ),
+            "input" if !spread && !inside_label && !self.is_labelled_input(element) => self.report(
+                Lint::MissingLabel,
+                span,
+                "<input> has no associated <label>, `aria-label` or `aria-labelledby`".to_string(),
+            ),
+            "a" if !spread && element.attribute("href").is_none() && is_used_as_button(element) => {
+                self.report(
+                    Lint::AnchorAsButton,
+                    span,
+                    "<a> without `href` is used as a button. Use <button> instead".to_string(),
+                )
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = tag_name[1..].parse::<u32>().unwrap();
+                if let Some(previous_level) = self.previous_heading_level {
+                    if level > previous_level + 1 {
+                        self.report(
+                            Lint::HeadingOrder,
+                            span,
+                            format!(
+                                "<{}> skips a heading level after <h{}>",
+                                tag_name, previous_level
+                            ),
+                        );
+                    }
+                }
+                self.previous_heading_level = Some(level);
+            }
+            _ => {}
+        }
+    }
+
+    fn check_aria(&mut self, element: &Element) {
+        for attribute in element.attributes() {
+            let name = attribute.name();
+            let span = attribute.ident().span();
+
+            if name.starts_with("aria-") && !html_spec::ARIA_ATTRIBUTES.contains(&name.as_str()) {
+                let message = match html_spec::closest_match(
+                    &name,
+                    html_spec::ARIA_ATTRIBUTES.iter().copied(),
+                ) {
+                    Some(known) => format!(
+                        "Unknown ARIA attribute `{}`. Did you mean `{}`?",
+                        name, known
+                    ),
+                    None => format!("Unknown ARIA attribute `{}`", name),
+                };
+                self.report(Lint::InvalidAria, span, message);
+            }
+
+            if name == "role" {
+                let roles = attribute.literal_value().unwrap_or_default();
+                for role in roles.split_whitespace() {
+                    let is_known = html_spec::ARIA_ROLES.contains(&role)
+                        || html_spec::ARIA_ROLE_PREFIXES
+                            .iter()
+                            .any(|prefix| role.starts_with(prefix));
+                    if !is_known {
+                        self.report(
+                            Lint::InvalidAria,
+                            span,
+                            format!("Unknown ARIA role `{}`", role),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_labelled_input(&self, element: &Element) -> bool {
+        let input_type = element
+            .attribute("type")
+            .and_then(|attribute| attribute.literal_value())
+            .map(|value| value.to_ascii_lowercase());
+        let needs_label = !matches!(
+            input_type.as_deref(),
+            Some("hidden" | "submit" | "reset" | "button" | "image")
+        );
+
+        let has_label_attribute = ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|name| element.attribute(name).is_some());
+
+        let has_label_element = match element.attribute("id") {
+            None => false,
+            Some(id) => match id.literal_value() {
+                Some(id) => self.label_targets.contains(&id),
+                None => self.has_dynamic_label_target,
+            },
+        };
+
+        !needs_label || has_label_attribute || has_label_element
+    }
+}
+
+fn is_used_as_button(element: &Element) -> bool {
+    let has_button_role = element
+        .attribute("role")
+        .and_then(|attribute| attribute.literal_value())
+        .map(|role| role.split_whitespace().any(|role| role == "button"))
+        .unwrap_or(false);
+
+    has_button_role || element.attribute("onclick").is_some()
+}
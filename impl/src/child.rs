@@ -1,16 +1,76 @@
+use crate::comment::{Comment, ConditionalComment};
 use crate::element::Element;
+use crate::entity::Entity;
+use crate::text::Text;
+use proc_macro_error::emit_error;
 use quote::{quote, ToTokens};
 use syn::parse::{Parse, ParseStream, Result};
 
 pub enum Child {
     Element(Element),
+    Comment(Comment),
+    ConditionalComment(ConditionalComment),
+    Entity(Entity),
+    Text(Text),
     RawBlock(syn::Block),
+    /// A `@let` binding, in scope for the following siblings
+    Binding(syn::Local),
+}
+
+impl Child {
+    /// The pre-escaped HTML of a child known at compile time, like text or a static
+    /// element
+    pub fn static_html(&self) -> Option<String> {
+        match self {
+            Self::Element(element) => element.static_html(),
+            Self::Comment(comment) => comment.static_html(),
+            Self::ConditionalComment(comment) => comment.static_html(),
+            Self::Entity(entity) => entity.static_html(),
+            Self::Text(text) => text.static_html(),
+            Self::RawBlock(_) | Self::Binding(_) => None,
+        }
+    }
+
+    /// Keeps the whitespace of the text in this node and its descendants as written
+    pub fn preserve_whitespace(&mut self) {
+        match self {
+            Self::Element(element) => element.preserve_whitespace(),
+            Self::Text(text) => text.preserve_whitespace(),
+            Self::ConditionalComment(comment) => comment.preserve_whitespace(),
+            Self::Comment(_) | Self::Entity(_) | Self::RawBlock(_) | Self::Binding(_) => {}
+        }
+    }
+}
+
+/// Parses the `#[whitespace(preserve)]` attribute of a node
+fn parse_whitespace_attribute(input: ParseStream) -> Result<bool> {
+    let mut preserve_whitespace = false;
+    for attribute in input.call(syn::Attribute::parse_outer)? {
+        let is_preserve = attribute.path.is_ident("whitespace")
+            && attribute
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|mode| mode == "preserve");
+        if is_preserve {
+            preserve_whitespace = true;
+        } else {
+            emit_error!(
+                attribute,
+                "Unknown attribute. Expected `#[whitespace(preserve)]`"
+            );
+        }
+    }
+
+    Ok(preserve_whitespace)
 }
 
 impl ToTokens for Child {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         match self {
             Self::Element(element) => element.to_tokens(tokens),
+            Self::Comment(comment) => comment.to_tokens(tokens),
+            Self::ConditionalComment(comment) => comment.to_tokens(tokens),
+            Self::Entity(entity) => entity.to_tokens(tokens),
+            Self::Text(text) => text.to_tokens(tokens),
             Self::RawBlock(block) => {
                 let ts = if block.stmts.len() == 1 {
                     let first = &block.stmts[0];
@@ -20,18 +80,66 @@ impl ToTokens for Child {
                 };
                 ts.to_tokens(tokens);
             }
+            Self::Binding(local) => local.to_tokens(tokens),
         }
     }
 }
 
 impl Parse for Child {
     fn parse(input: ParseStream) -> Result<Self> {
-        match input.parse::<Element>() {
-            Ok(element) => Ok(Self::Element(element)),
-            Err(_) => {
-                let block = input.parse::<syn::Block>()?;
-                Ok(Self::RawBlock(block))
+        if input.peek(syn::Token![#]) {
+            let preserve_whitespace = parse_whitespace_attribute(input)?;
+            let mut child = input.parse::<Child>()?;
+            if preserve_whitespace {
+                child.preserve_whitespace();
             }
+            return Ok(child);
+        }
+
+        if ConditionalComment::peek(input) {
+            return Ok(Self::ConditionalComment(input.parse()?));
+        }
+
+        if Comment::peek(input) {
+            return Ok(Self::Comment(input.parse()?));
+        }
+
+        if Text::peek_space(input) {
+            return Ok(Self::Text(Text::parse_space(input)?));
+        }
+
+        if input.peek(syn::LitStr) {
+            return Ok(Self::Text(input.parse()?));
+        }
+
+        if Entity::peek(input) {
+            return Ok(Self::Entity(input.parse()?));
+        }
+
+        if input.peek(syn::Token![@]) && input.peek2(syn::Token![let]) {
+            input.parse::<syn::Token![@]>()?;
+            return match input.parse::<syn::Stmt>()? {
+                syn::Stmt::Local(local) if local.init.is_some() => Ok(Self::Binding(local)),
+                stmt => Err(syn::Error::new_spanned(
+                    stmt,
+                    "Expected a binding with a value, like `@let total = items.len();`",
+                )),
+            };
+        }
+
+        // Blocks never start with `<`, so element errors are reported as they are
+        if input.peek(syn::Token![<]) {
+            return Ok(Self::Element(input.parse()?));
+        }
+
+        // A string literal in a block is text, like a bare one
+        let block = input.parse::<syn::Block>()?;
+        match block.stmts.as_slice() {
+            [syn::Stmt::Expr(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(text),
+                ..
+            }))] => Ok(Self::Text(Text::new(text.value()))),
+            _ => Ok(Self::RawBlock(block)),
         }
     }
 }
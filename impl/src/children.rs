@@ -1,5 +1,12 @@
 use crate::child::Child;
-use quote::{quote, ToTokens};
+use crate::comment::ConditionalComment;
+use crate::element::static_html_tokens;
+use crate::tags::ClosingTag;
+use crate::text::Text;
+use proc_macro2::{LineColumn, Span};
+use quote::{format_ident, quote, ToTokens};
+use syn::buffer::Cursor;
+use syn::parse::discouraged::Speculative;
 use syn::parse::{Parse, ParseStream, Result};
 
 #[derive(Default)]
@@ -16,41 +23,173 @@ impl Children {
         self.nodes.len()
     }
 
-    pub fn as_option_of_tuples_tokens(&self) -> proc_macro2::TokenStream {
-        let children_quotes: Vec<_> = self
-            .nodes
-            .iter()
-            .map(|child| {
-                quote! { #child }
-            })
-            .collect();
-
-        match children_quotes.len() {
-            0 => quote! { Option::<()>::None },
-            1 => quote! { Some(#(#children_quotes),*) },
-            _ => {
-                let mut iter = children_quotes.iter();
+    pub fn preserve_whitespace(&mut self) {
+        self.nodes.iter_mut().for_each(Child::preserve_whitespace);
+    }
+
+    /// The pre-escaped HTML of all the children, if they are all known at compile time
+    pub fn static_html(&self) -> Option<String> {
+        self.nodes.iter().map(Child::static_html).collect()
+    }
+
+    /// Collects the plain elements among the children, see `Element::static_elements`
+    pub fn static_elements(&self, depth: usize, elements: &mut Vec<(usize, String)>) {
+        for child in &self.nodes {
+            if let Child::Element(element) = child {
+                element.static_elements(depth, elements);
+            }
+        }
+    }
+
+    /// Lowers the children to an `Option` of a single renderable. Siblings are
+    /// evaluated in order and written sequentially by a `tide_jsx::RenderFn` closure,
+    /// instead of nesting them in tuples, to keep the generated types small.
+    /// `@let` bindings are evaluated in the closure, between the siblings around
+    /// them, so the following siblings can borrow them.
+    pub fn as_option_tokens(&self) -> proc_macro2::TokenStream {
+        let mut children_quotes: Vec<_> = vec![];
+        let mut static_html = String::new();
+        let mut static_elements = Vec::new();
+
+        // Consecutive static children are written at once
+        for child in &self.nodes {
+            if let Child::Binding(local) = child {
+                children_quotes.push(Lowered::Binding(quote! { #local }));
+                continue;
+            }
+
+            match child.static_html() {
+                Some(html) => {
+                    static_html.push_str(&html);
+                    if let Child::Element(element) = child {
+                        element.static_elements(0, &mut static_elements);
+                    }
+                }
+                None => {
+                    if !static_html.is_empty() {
+                        children_quotes.push(Lowered::Child(static_html_tokens(
+                            &static_html,
+                            &static_elements,
+                        )));
+                        static_html.clear();
+                        static_elements.clear();
+                    }
+                    children_quotes.push(Lowered::Child(quote! { #child }));
+                }
+            }
+        }
+        if !static_html.is_empty() {
+            children_quotes.push(Lowered::Child(static_html_tokens(
+                &static_html,
+                &static_elements,
+            )));
+        }
 
-                let first = iter.next().unwrap();
-                let second = iter.next().unwrap();
+        match children_quotes.as_slice() {
+            [] => quote! { Option::<()>::None },
+            [Lowered::Child(child)] => quote! { Some(#child) },
+            _ => {
+                // Mixed-site hygiene keeps these bindings from shadowing user variables
+                let writer = syn::Ident::new("writer", Span::mixed_site());
+                let mut names = vec![];
+                let mut statements = vec![];
+                let mut rendered = vec![];
+                for lowered in children_quotes {
+                    match lowered {
+                        // From the first binding on, children are built while rendering,
+                        // so they can borrow the bindings before them
+                        Lowered::Binding(binding) => rendered.push(binding),
+                        Lowered::Child(child) if !rendered.is_empty() => rendered.push(quote! {
+                            tide_jsx::Render::render_into(&#child, #writer)?;
+                        }),
+                        Lowered::Child(child) => {
+                            let name =
+                                format_ident!("child_{}", names.len(), span = Span::mixed_site());
+                            statements.push(quote! { let #name = #child; });
+                            names.push(name);
+                        }
+                    }
+                }
 
-                let tuple_of_tuples = iter.fold(
-                    quote!((#first, #second)),
-                    |renderable, current| quote!((#renderable, #current)),
-                );
+                let contents = match names.as_slice() {
+                    [name] if rendered.is_empty() => quote! { Some(#name) },
+                    _ => quote! {
+                        Some(tide_jsx::RenderFn(move |#writer: &mut String| -> ::std::fmt::Result {
+                            #(tide_jsx::Render::render_into(&#names, #writer)?;)*
+                            #(#rendered)*
+                            Ok(())
+                        }))
+                    },
+                };
 
-                quote! { Some(#tuple_of_tuples) }
+                quote! {{
+                    #(#statements)*
+                    #contents
+                }}
             }
         }
     }
 }
 
+/// A child lowered to tokens: a renderable expression, or a `let` statement
+enum Lowered {
+    Child(proc_macro2::TokenStream),
+    Binding(proc_macro2::TokenStream),
+}
+
 impl Parse for Children {
     fn parse(input: ParseStream) -> Result<Self> {
-        let mut nodes = vec![];
+        let mut nodes: Vec<Child> = vec![];
+        // Where the previous sibling ends, to find the whitespace before the next one
+        let mut previous_end = None;
+
+        while !input.is_empty() {
+            // The end of a conditional comment ends its children
+            if ConditionalComment::peek_end(input) {
+                break;
+            }
+
+            if input.peek(syn::Token![<]) && input.peek2(syn::Token![/]) {
+                // A closing tag ends the children, unless it closes a void sibling
+                // opened without `/>`, which is reported and skipped
+                let fork = input.fork();
+                let closing_name = match fork.parse::<ClosingTag>() {
+                    Ok(closing_tag) => closing_tag.name.as_string(),
+                    Err(_) => break,
+                };
+                let reported = nodes.iter().enumerate().rev().any(|(index, child)| {
+                    matches!(child, Child::Element(element)
+                        if element.report_void_closing_tag(&closing_name, index + 1 < nodes.len()))
+                });
+                if !reported {
+                    break;
+                }
+                input.advance_to(&fork);
+                previous_end = None;
+                continue;
+            }
 
-        while !input.peek(syn::Token![<]) || !input.peek2(syn::Token![/]) {
+            let start = input.span().start();
+            let begin = input.cursor();
             let child = input.parse::<Child>()?;
+
+            // Bindings don't render anything, so whitespace around them is dropped
+            if let Child::Binding(_) = child {
+                previous_end = None;
+            } else {
+                if let Some(end) = previous_end {
+                    let previous = match nodes.last() {
+                        Some(Child::Text(text)) => Some(text),
+                        _ => None,
+                    };
+                    let next = match &child {
+                        Child::Text(text) => Some(text),
+                        _ => None,
+                    };
+                    nodes.extend(Text::gap(end, start, previous, next).map(Child::Text));
+                }
+                previous_end = last_token_end(begin, input.cursor());
+            }
             nodes.push(child);
         }
 
@@ -58,8 +197,21 @@ impl Parse for Children {
     }
 }
 
+/// Where the last token before `end` ends, walking from `begin`
+fn last_token_end(begin: Cursor, end: Cursor) -> Option<LineColumn> {
+    let mut cursor = begin;
+    let mut last = None;
+    while cursor != end {
+        let (token, next) = cursor.token_tree()?;
+        last = Some(token.span().end());
+        cursor = next;
+    }
+
+    last
+}
+
 impl ToTokens for Children {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        self.as_option_of_tuples_tokens().to_tokens(tokens);
+        self.as_option_tokens().to_tokens(tokens);
     }
 }
@@ -0,0 +1,216 @@
+use crate::children::Children;
+use crate::html_escaping::escape_comment;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Result};
+
+/// An HTML comment, with a string literal like `<!-- "text" -->` or a block
+/// like `<!-- {text} -->`
+pub struct Comment {
+    text: syn::Block,
+}
+
+impl Comment {
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(syn::Token![<]) && input.peek2(syn::Token![!])
+    }
+
+    fn literal_text(&self) -> Option<String> {
+        match self.text.stmts.as_slice() {
+            [syn::Stmt::Expr(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(text),
+                ..
+            }))] => Some(text.value()),
+            _ => None,
+        }
+    }
+
+    /// The markup of a comment with literal text, or nothing with the
+    /// `strip-comments` feature
+    pub fn static_html(&self) -> Option<String> {
+        let text = self.literal_text()?;
+        if cfg!(feature = "strip-comments") {
+            return Some(String::new());
+        }
+
+        Some(format!("<!-- {} -->", escape_comment(&text)))
+    }
+}
+
+impl Parse for Comment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<syn::Token![<]>()?;
+        input.parse::<syn::Token![!]>()?;
+        input.parse::<syn::Token![-]>()?;
+        input.parse::<syn::Token![-]>()?;
+
+        let text = if input.peek(syn::LitStr) {
+            let text = input.parse::<syn::LitStr>()?;
+            syn::parse_quote!({ #text })
+        } else {
+            input.parse::<syn::Block>()?
+        };
+
+        input.parse::<syn::Token![-]>()?;
+        input.parse::<syn::Token![-]>()?;
+        input.parse::<syn::Token![>]>()?;
+        Ok(Self { text })
+    }
+}
+
+impl ToTokens for Comment {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let text = match self.text.stmts.as_slice() {
+            [syn::Stmt::Expr(expr)] => quote!(#expr),
+            _ => {
+                let text = &self.text;
+                quote!(#text)
+            }
+        };
+        // Stripped comments still evaluate their text, so it is type-checked and
+        // its variables are used
+        let declaration = if cfg!(feature = "strip-comments") {
+            quote! {{
+                let _ = tide_jsx::Comment::new(#text);
+            }}
+        } else {
+            quote! { tide_jsx::Comment::new(#text) }
+        };
+
+        declaration.to_tokens(tokens);
+    }
+}
+
+/// A conditional comment, like `<!--[if mso]> ... <![endif]-->`, with a condition
+/// written as words like `[if gte mso 9]` or as a string literal like
+/// `[if "(gt IE 5)&(lt IE 7)"]`
+pub struct ConditionalComment {
+    condition: String,
+    children: Children,
+}
+
+impl ConditionalComment {
+    pub fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        Comment::peek(&fork)
+            && fork.parse::<syn::Token![<]>().is_ok()
+            && fork.parse::<syn::Token![!]>().is_ok()
+            && fork.parse::<syn::Token![-]>().is_ok()
+            && fork.parse::<syn::Token![-]>().is_ok()
+            && fork.peek(syn::token::Bracket)
+    }
+
+    /// Whether the input starts with the `<![endif]-->` ending a conditional comment
+    pub fn peek_end(input: ParseStream) -> bool {
+        input.peek(syn::Token![<])
+            && input.peek2(syn::Token![!])
+            && input.peek3(syn::token::Bracket)
+    }
+
+    pub fn preserve_whitespace(&mut self) {
+        self.children.preserve_whitespace();
+    }
+
+    /// The conditional comment markup, when its contents are known at compile time.
+    /// Unlike plain comments, it is kept with the `strip-comments` feature.
+    pub fn static_html(&self) -> Option<String> {
+        let contents = self.children.static_html()?;
+        Some(format!(
+            "<!--[if {}]>{}<![endif]-->",
+            self.condition, contents
+        ))
+    }
+
+    fn parse_condition(input: ParseStream) -> Result<String> {
+        input.parse::<syn::Token![if]>()?;
+        if input.peek(syn::LitStr) {
+            let condition = input.parse::<syn::LitStr>()?;
+            if !is_valid_condition(&condition.value()) {
+                return Err(syn::Error::new_spanned(
+                    condition,
+                    "Conditions can only have letters, digits, dots, spaces and the `!`, `&`, `|`, `(` and `)` operators",
+                ));
+            }
+            return Ok(condition.value());
+        }
+
+        let mut condition = String::new();
+        while !input.is_empty() {
+            if !condition.is_empty() && !condition.ends_with('!') {
+                condition.push(' ');
+            }
+            if input.peek(syn::Token![!]) {
+                input.parse::<syn::Token![!]>()?;
+                condition.push('!');
+            } else if input.peek(syn::Ident::peek_any) {
+                condition.push_str(&input.call(syn::Ident::parse_any)?.to_string());
+            } else if input.peek(syn::LitInt) && !input.peek(syn::Token![-]) {
+                condition.push_str(&input.parse::<syn::LitInt>()?.to_string());
+            } else if input.peek(syn::LitFloat) && !input.peek(syn::Token![-]) {
+                condition.push_str(&input.parse::<syn::LitFloat>()?.to_string());
+            } else {
+                return Err(input.error(
+                    "Expected a condition like `[if mso]` or `[if lt IE 9]`. Write other conditions as a string literal, like `[if \"(gt IE 5)&(lt IE 7)\"]`",
+                ));
+            }
+        }
+
+        if condition.is_empty() || condition.ends_with('!') {
+            return Err(input.error("Expected a condition, like `[if mso]`"));
+        }
+        Ok(condition)
+    }
+}
+
+/// Whether a condition can be written in a conditional comment as is, matching
+/// `tide_jsx::ConditionalComment`
+fn is_valid_condition(condition: &str) -> bool {
+    !condition.trim().is_empty()
+        && condition
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " .!&|()".contains(c))
+}
+
+impl Parse for ConditionalComment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<syn::Token![<]>()?;
+        input.parse::<syn::Token![!]>()?;
+        input.parse::<syn::Token![-]>()?;
+        input.parse::<syn::Token![-]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let condition = Self::parse_condition(&content)?;
+        input.parse::<syn::Token![>]>()?;
+
+        let children = input.parse::<Children>()?;
+
+        if !Self::peek_end(input) {
+            return Err(input.error("Expected `<![endif]-->` to end the conditional comment"));
+        }
+        input.parse::<syn::Token![<]>()?;
+        input.parse::<syn::Token![!]>()?;
+        let content;
+        let bracket = syn::bracketed!(content in input);
+        match content.parse::<syn::Ident>() {
+            Ok(ident) if ident == "endif" && content.is_empty() => {}
+            _ => return Err(syn::Error::new(bracket.span, "Expected `[endif]`")),
+        }
+        input.parse::<syn::Token![-]>()?;
+        input.parse::<syn::Token![-]>()?;
+        input.parse::<syn::Token![>]>()?;
+
+        Ok(Self {
+            condition,
+            children,
+        })
+    }
+}
+
+impl ToTokens for ConditionalComment {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let condition = &self.condition;
+        let children = self.children.as_option_tokens();
+        let declaration = quote! { tide_jsx::ConditionalComment::new(#condition, #children) };
+        declaration.to_tokens(tokens);
+    }
+}
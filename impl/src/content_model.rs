@@ -0,0 +1,152 @@
+// The HTML content model rules checked by `rsx!`. This file is also expanded into
+// the runtime crate by `content_model!()`, so it only uses `std` and outer doc comments.
+
+/// Elements that must be direct children of one of the listed parents
+pub(crate) const REQUIRED_PARENTS: &[(&str, &[&str])] = &[
+    ("li", &["ul", "ol", "menu"]),
+    ("dt", &["dl", "div"]),
+    ("dd", &["dl", "div"]),
+    ("caption", &["table"]),
+    ("colgroup", &["table"]),
+    ("thead", &["table"]),
+    ("tbody", &["table"]),
+    ("tfoot", &["table"]),
+    ("tr", &["table", "thead", "tbody", "tfoot"]),
+    ("td", &["tr"]),
+    ("th", &["tr"]),
+    ("optgroup", &["select"]),
+    ("option", &["select", "datalist", "optgroup"]),
+    ("figcaption", &["figure"]),
+    ("legend", &["fieldset"]),
+    ("summary", &["details"]),
+];
+
+/// Table parts that browsers drop, keeping only their contents, outside of their
+/// required parents
+pub(crate) const DROPPED_OUTSIDE_PARENTS: &[&str] = &[
+    "caption", "colgroup", "thead", "tbody", "tfoot", "tr", "td", "th",
+];
+
+/// Elements that can't be direct children of the given parent, because browsers
+/// close the parent before them
+pub(crate) const FORBIDDEN_CHILDREN: &[(&str, &[&str])] = &[(
+    "p",
+    &[
+        "address",
+        "article",
+        "aside",
+        "blockquote",
+        "details",
+        "dialog",
+        "div",
+        "dl",
+        "fieldset",
+        "figcaption",
+        "figure",
+        "footer",
+        "form",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "header",
+        "hgroup",
+        "hr",
+        "main",
+        "menu",
+        "nav",
+        "ol",
+        "p",
+        "pre",
+        "search",
+        "section",
+        "table",
+        "ul",
+    ],
+)];
+
+/// Elements that can't appear anywhere inside the given ancestor, because browsers
+/// close or drop one of them
+pub(crate) const FORBIDDEN_DESCENDANTS: &[(&str, &[&str])] =
+    &[("a", &["a"]), ("button", &["button"]), ("form", &["form"])];
+
+/// Elements that aren't allowed inside the given ancestor, but that browsers keep
+/// where they are written
+pub(crate) const INVALID_DESCENDANTS: &[(&str, &[&str])] =
+    &[("button", &["a"]), ("label", &["label"])];
+
+/// An element placed where the HTML content model doesn't allow it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestingError {
+    pub message: String,
+    /// Whether browsers parse the markup into a different tree than the one written,
+    /// like closing a `<p>` before a `<div>`, instead of keeping the invalid nesting
+    pub changes_tree: bool,
+}
+
+impl std::fmt::Display for NestingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Checks that a `<tag_name>` element can be placed inside `ancestors`, ordered
+/// from the outermost to the direct parent
+pub fn nesting_error(ancestors: &[&str], tag_name: &str) -> Option<NestingError> {
+    if let Some(parent) = ancestors.last() {
+        let required_parents = REQUIRED_PARENTS
+            .iter()
+            .find(|(child, _)| *child == tag_name);
+        if let Some((_, parents)) = required_parents {
+            if !parents.contains(parent) {
+                return Some(NestingError {
+                    message: format!(
+                        "<{}> can't be a child of <{}>. Expected it inside {}",
+                        tag_name,
+                        parent,
+                        parents
+                            .iter()
+                            .map(|parent| format!("<{}>", parent))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    // Like an `<li>` right inside another one, which closes it
+                    changes_tree: DROPPED_OUTSIDE_PARENTS.contains(&tag_name)
+                        || *parent == tag_name,
+                });
+            }
+        }
+
+        let forbidden = FORBIDDEN_CHILDREN
+            .iter()
+            .any(|(forbidding, children)| forbidding == parent && children.contains(&tag_name));
+        if forbidden {
+            return Some(NestingError {
+                message: format!(
+                    "<{}> can't be a child of <{}>. Browsers close the <{}> before it",
+                    tag_name, parent, parent
+                ),
+                changes_tree: true,
+            });
+        }
+    }
+
+    let descendant_error = |rules: &[(&str, &[&str])], changes_tree: bool| {
+        ancestors.iter().find_map(|ancestor| {
+            rules
+                .iter()
+                .find(|(forbidding, descendants)| {
+                    forbidding == ancestor && descendants.contains(&tag_name)
+                })
+                .map(|_| NestingError {
+                    message: format!("<{}> can't be nested inside <{}>", tag_name, ancestor),
+                    changes_tree,
+                })
+        })
+    };
+
+    descendant_error(FORBIDDEN_DESCENDANTS, true)
+        .or_else(|| descendant_error(INVALID_DESCENDANTS, false))
+}
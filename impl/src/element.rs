@@ -1,26 +1,59 @@
+use crate::accessibility::{Diagnostic, Lint};
+use crate::child::Child;
 use crate::children::Children;
+use crate::content_model;
+use crate::element_attribute::{Directive, ElementAttribute};
 use crate::element_attributes::ElementAttributes;
-use crate::tags::{ClosingTag, FallbackAttributes, OpenTag};
+use crate::html_escaping::escape_html;
+use crate::html_spec;
+use crate::tags::{FallbackAttributes, OpenTag, TagName};
+use proc_macro2::Span;
+use proc_macro_error::emit_error;
 use quote::{quote, ToTokens};
 use syn::parse::{Parse, ParseStream, Result};
+use syn::spanned::Spanned;
 
 pub struct Element {
-    name: syn::Path,
+    name: TagName,
     attributes: ElementAttributes,
     fallback_attributes: Option<FallbackAttributes>,
     children: Children,
+    /// The name in the closing tag, when it matches the opening tag
+    closing_name: Option<TagName>,
+    /// Whether the element was never closed, and its children are a best guess
+    never_closed: bool,
+    /// Whether this is a void element opened without `/>`, like `<br>`
+    unclosed_void: bool,
 }
 
 impl Parse for Element {
     fn parse(input: ParseStream) -> Result<Self> {
         let open_tag = input.parse::<OpenTag>()?;
 
+        let mut closing_name = None;
+        let mut never_closed = false;
+        let mut unclosed_void = false;
         let children = if open_tag.self_closing {
             Children::default()
+        } else if open_tag.is_void_element() {
+            // A stray closing tag is reported by the parent's children, see `Children::parse`
+            unclosed_void = true;
+            Children::default()
         } else {
-            let children = input.parse::<Children>()?;
-            let closing_tag = input.parse::<ClosingTag>()?;
-            closing_tag.validate(&open_tag);
+            let scope = open_tag.enter();
+            let mut children = input.parse::<Children>()?;
+            drop(scope);
+            if open_tag.preserves_whitespace() {
+                children.preserve_whitespace();
+            }
+            match open_tag.parse_closing_tag(input)? {
+                Some(closing_tag) => {
+                    if open_tag.name.as_string() == closing_tag.name.as_string() {
+                        closing_name = Some(closing_tag.name);
+                    }
+                }
+                None => never_closed = true,
+            }
             children
         };
 
@@ -29,40 +62,320 @@ impl Parse for Element {
             attributes: open_tag.attributes,
             fallback_attributes: open_tag.fallback_attributes,
             children,
+            closing_name,
+            never_closed,
+            unclosed_void,
         })
     }
 }
 
 impl Element {
+    /// A fragment wrapping sibling nodes, like the ones at the top level of `rsx!`
+    pub fn fragment(children: Children) -> Self {
+        Element {
+            name: TagName::fragment(),
+            attributes: ElementAttributes::default(),
+            fallback_attributes: None,
+            children,
+            closing_name: None,
+            never_closed: false,
+            unclosed_void: false,
+        }
+    }
+
+    /// Reports a closing tag matching this void element, like the `</br>` in
+    /// `<br>{"x"}</br>`, once the siblings in between are parsed. Returns `false`
+    /// when the element isn't a void element opened without `/>`.
+    pub fn report_void_closing_tag(&self, closing_name: &str, has_children: bool) -> bool {
+        if !self.unclosed_void || self.name.as_string() != closing_name {
+            return false;
+        }
+
+        let name = &self.name;
+        if has_children {
+            emit_error!(name, "Void element <{}> can't have children", quote!(#name));
+        } else {
+            emit_error!(
+                name,
+                "Void element <{}> can't have a closing tag. Try `<{} />` instead",
+                quote!(#name),
+                quote!(#name)
+            );
+        }
+        true
+    }
+
     pub fn is_custom_element(&self) -> bool {
-        match self.name.get_ident() {
-            None => true,
-            Some(ident) => {
-                let name = ident.to_string();
-                let first_letter = name.get(0..1).unwrap();
-                first_letter.to_uppercase() == first_letter
+        self.name.is_component()
+    }
+
+    pub fn name(&self) -> &TagName {
+        &self.name
+    }
+
+    /// The HTML tag name of a plain element, or `None` for components, fragments
+    /// and tag names only known at runtime
+    pub fn tag_name(&self) -> Option<String> {
+        if self.is_custom_element() || self.name.is_dynamic() {
+            None
+        } else {
+            Some(self.name.as_string())
+        }
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = &ElementAttribute> {
+        self.attributes.attributes.iter()
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&ElementAttribute> {
+        self.attributes().find(|attribute| attribute.name() == name)
+    }
+
+    /// Whether a plain element spreads attributes only known at runtime
+    pub fn has_spread_attributes(&self) -> bool {
+        self.fallback_attributes.is_some()
+    }
+
+    pub fn child_elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.nodes.iter().filter_map(|child| match child {
+            Child::Element(element) => Some(element),
+            Child::Comment(_)
+            | Child::ConditionalComment(_)
+            | Child::Entity(_)
+            | Child::Text(_)
+            | Child::RawBlock(_)
+            | Child::Binding(_) => None,
+        })
+    }
+
+    /// The pre-escaped HTML of a plain element whose attributes and children are
+    /// all literals, rendered the same way as `SimpleElement`
+    pub fn static_html(&self) -> Option<String> {
+        let tag_name = self.tag_name()?;
+        if self.has_spread_attributes() {
+            return None;
+        }
+
+        let merges_class = self
+            .attributes()
+            .any(|attribute| matches!(attribute.directive(), Some(Directive::Class(_))));
+        let mut class_written = false;
+
+        let mut html = format!("<{}", tag_name);
+        for attribute in self.attributes() {
+            // The merged class is written in place of the first of its parts
+            if merges_class && is_class_part(attribute) {
+                if !class_written {
+                    class_written = true;
+                    let classes = self.static_classes()?;
+                    if !classes.is_empty() {
+                        html.push_str(&format!(" class=\"{}\"", escape_html(&classes.join(" "))));
+                    }
+                }
+                continue;
+            }
+
+            if attribute.directive().is_some() {
+                return None;
+            }
+
+            match attribute.presence() {
+                Some(true) => html.push_str(&format!(" {}=\"\"", attribute.name())),
+                Some(false) => {}
+                None => {
+                    let value = attribute.literal_value()?;
+                    html.push_str(&format!(
+                        " {}=\"{}\"",
+                        attribute.name(),
+                        escape_html(&value)
+                    ));
+                }
+            }
+        }
+
+        if html_spec::is_void_element(&tag_name) {
+            html.push_str(" />");
+        } else {
+            let children = self.children.static_html()?;
+            html.push_str(&format!(">{}</{}>", children, tag_name));
+        }
+
+        Some(html)
+    }
+
+    /// Collects the plain elements of statically rendered markup in document order,
+    /// with their depth, for the nesting checks done while rendering in debug builds
+    pub fn static_elements(&self, depth: usize, elements: &mut Vec<(usize, String)>) {
+        match self.tag_name() {
+            Some(tag_name) => {
+                elements.push((depth, tag_name));
+                self.children.static_elements(depth + 1, elements);
+            }
+            None => self.children.static_elements(depth, elements),
+        }
+    }
+
+    /// The classes of a `class` attribute merged with literal `class:name` directives,
+    /// in order and without duplicates, the same way as `ClassList`
+    fn static_classes(&self) -> Option<Vec<String>> {
+        let mut classes: Vec<String> = Vec::new();
+        for attribute in self
+            .attributes()
+            .filter(|attribute| is_class_part(attribute))
+        {
+            let value = match attribute.directive() {
+                Some(Directive::Class(class)) if attribute.literal_bool()? => class,
+                Some(_) => continue,
+                None => attribute.literal_value()?,
+            };
+            for class in value.split_whitespace() {
+                if !classes.iter().any(|existing| existing == class) {
+                    classes.push(class.to_string());
+                }
+            }
+        }
+
+        Some(classes)
+    }
+
+    /// Keeps the whitespace of the text in this element as written
+    pub fn preserve_whitespace(&mut self) {
+        self.children.preserve_whitespace();
+    }
+
+    pub fn is_fragment(&self) -> bool {
+        self.name.is_fragment()
+    }
+
+    /// Reports invalid nesting of plain elements, like `<div>` inside `<p>`.
+    /// Fragments are transparent, while component children start over with no
+    /// known ancestors, since they may be rendered anywhere.
+    pub fn check_content_model(&self, ancestors: &mut Vec<String>, warnings: &mut Vec<Diagnostic>) {
+        match self.tag_name() {
+            None if self.is_fragment() => self.check_children_content_model(ancestors, warnings),
+            None => self.check_children_content_model(&mut Vec::new(), warnings),
+            Some(tag_name) => {
+                let ancestor_names: Vec<_> = ancestors.iter().map(String::as_str).collect();
+                match content_model::nesting_error(&ancestor_names, &tag_name) {
+                    Some(error) if error.changes_tree => {
+                        emit_error!(self.name, "Invalid nesting: {}", error);
+                    }
+                    Some(error) => warnings.push(Diagnostic {
+                        lint: Lint::InvalidNesting,
+                        span: self.name.span(),
+                        message: format!("Invalid nesting: {}", error),
+                    }),
+                    None => {}
+                }
+
+                ancestors.push(tag_name);
+                self.check_children_content_model(ancestors, warnings);
+                ancestors.pop();
             }
         }
     }
+
+    fn check_children_content_model(
+        &self,
+        ancestors: &mut Vec<String>,
+        warnings: &mut Vec<Diagnostic>,
+    ) {
+        // The siblings swallowed by an unclosed element would be reported as misplaced
+        if self.never_closed {
+            return;
+        }
+
+        for element in self.child_elements() {
+            element.check_content_model(ancestors, warnings);
+        }
+    }
+}
+
+/// Statically rendered markup, as a `tide_jsx::StaticHtml` when it has elements
+/// whose nesting is checked while rendering in debug builds
+pub fn static_html_tokens(html: &str, elements: &[(usize, String)]) -> proc_macro2::TokenStream {
+    if elements.is_empty() {
+        return quote! { tide_jsx::Raw::from(#html) };
+    }
+
+    let elements = elements
+        .iter()
+        .map(|(depth, tag_name)| quote! { (#depth, #tag_name) });
+    quote! { tide_jsx::StaticHtml::new(#html, &[#(#elements),*]) }
+}
+
+/// Whether an attribute is merged into the `class` attribute
+fn is_class_part(attribute: &ElementAttribute) -> bool {
+    match attribute.directive() {
+        Some(directive) => matches!(directive, Directive::Class(_)),
+        None => attribute.name() == "class",
+    }
 }
 
 impl ToTokens for Element {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let name = &self.name;
 
-        let declaration = if self.is_custom_element() {
+        let declaration = if let Some(html) = self.static_html() {
+            let mut elements = Vec::new();
+            self.static_elements(0, &mut elements);
+            static_html_tokens(&html, &elements)
+        } else if self.is_fragment() && self.children.len() == 0 {
+            // Like `rsx! {}` or `<></>`, which have no children to build the fragment with
+            quote! { tide_jsx::Fragment { children: () } }
+        } else if self.is_custom_element() {
+            let attrs = self.attributes.for_custom_element(
+                name.span(),
+                self.fallback_attributes.as_ref(),
+                &self.children,
+            );
+            let component = quote! { #name #attrs };
+
+            // Matching the closing tag name against the component lets IDEs
+            // navigate and rename through it, like the opening one. `if let`
+            // accepts enum variants, like `</Widget::Chart>`, along with structs.
+            match &self.closing_name {
+                Some(closing_name) if !self.is_fragment() => {
+                    let binding = syn::Ident::new("component", Span::mixed_site());
+                    quote! {{
+                        let #binding = #component;
+                        #[allow(irrefutable_let_patterns)]
+                        if let #closing_name { .. } = &#binding {}
+                        #binding
+                    }}
+                }
+                _ => component,
+            }
+        } else {
             let attrs = self
                 .attributes
-                .for_custom_element(self.fallback_attributes.as_ref(), &self.children);
-            quote! { #name #attrs }
-        } else {
-            let attrs = self.attributes.for_simple_element();
-            let children_tuple = self.children.as_option_of_tuples_tokens();
-            quote! {
-                tide_jsx::SimpleElement {
-                    tag_name: stringify!(#name),
-                    attributes: #attrs,
-                    contents: #children_tuple,
+                .for_simple_element(self.fallback_attributes.as_ref());
+            let children = self.children.as_option_tokens();
+
+            match &self.name {
+                // Tag names and component factories picked at runtime
+                TagName::Dynamic(block) => {
+                    let tag = match block.stmts.as_slice() {
+                        [syn::Stmt::Expr(expr)] => quote!(#expr),
+                        _ => quote!(#block),
+                    };
+                    quote! {
+                        tide_jsx::DynamicElement {
+                            tag: #tag,
+                            attributes: #attrs,
+                            contents: #children,
+                        }
+                    }
+                }
+                name => {
+                    let tag_name = name.as_string();
+                    quote! {
+                        tide_jsx::SimpleElement {
+                            tag_name: #tag_name,
+                            attributes: #attrs,
+                            contents: #children,
+                        }
+                    }
                 }
             }
         };
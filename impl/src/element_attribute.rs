@@ -1,14 +1,146 @@
-use quote::quote;
+use crate::html_spec;
+use crate::tags::TagName;
+use quote::{quote, ToTokens};
 use std::hash::{Hash, Hasher};
 use syn::ext::IdentExt;
+use syn::parse::discouraged::Speculative;
 use syn::parse::{Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
-pub type AttributeKey = syn::punctuated::Punctuated<syn::Ident, syn::Token![-]>;
+/// The name of an attribute, as written in the markup
+pub enum AttributeKey {
+    /// Identifiers separated by dashes, like `class` or `data-id`
+    Dashed(Punctuated<syn::Ident, syn::Token![-]>),
+    /// A namespaced or framework-specific name, like `xlink:href`, `@click`,
+    /// `:class` or `x-on:click.prevent`, with the tokens it was written with
+    Verbatim(String, proc_macro2::TokenStream),
+    /// A name written as a string literal, like `"@click"`, for anything else
+    Lit(syn::LitStr),
+}
+
+impl AttributeKey {
+    /// The identifiers of a dash-separated key
+    pub fn idents(&self) -> Vec<&syn::Ident> {
+        match self {
+            Self::Dashed(idents) => idents.iter().collect(),
+            Self::Verbatim(..) | Self::Lit(_) => Vec::new(),
+        }
+    }
+
+    /// The HTML attribute name, with raw identifiers unwrapped
+    pub fn name(&self) -> String {
+        match self {
+            Self::Dashed(idents) => idents
+                .iter()
+                .map(|ident| ident.unraw().to_string())
+                .collect::<Vec<_>>()
+                .join("-"),
+            Self::Verbatim(name, _) => name.clone(),
+            Self::Lit(name) => name.value(),
+        }
+    }
+
+    /// Parses a `-`, `:`, `::` or `.` separator followed by an identifier, along
+    /// with the tokens it was written with
+    fn parse_separator(input: ParseStream) -> Option<(String, proc_macro2::TokenStream)> {
+        let fork = input.fork();
+        let mut written = proc_macro2::TokenStream::new();
+        let separator = if fork.peek(syn::Token![::]) {
+            fork.parse::<syn::Token![::]>()
+                .ok()?
+                .to_tokens(&mut written);
+            "::".to_string()
+        } else if fork.peek(syn::Token![-])
+            || fork.peek(syn::Token![:])
+            || fork.peek(syn::Token![.])
+        {
+            let punct = fork.parse::<proc_macro2::Punct>().ok()?;
+            written.extend(Some(proc_macro2::TokenTree::from(punct.clone())));
+            punct.as_char().to_string()
+        } else {
+            return None;
+        };
+
+        if !fork.peek(syn::Ident::peek_any) {
+            return None;
+        }
+        input.advance_to(&fork);
+        Some((separator, written))
+    }
+}
+
+impl ToTokens for AttributeKey {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::Dashed(idents) => idents.to_tokens(tokens),
+            Self::Verbatim(_, written) => written.to_tokens(tokens),
+            Self::Lit(name) => name.to_tokens(tokens),
+        }
+    }
+}
+
+impl Parse for AttributeKey {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(syn::LitStr) {
+            return Ok(Self::Lit(input.parse()?));
+        }
+
+        let mut name = String::new();
+        let mut written = proc_macro2::TokenStream::new();
+        let mut idents = Punctuated::new();
+        let mut is_dashed = true;
+
+        if input.peek(syn::Token![@]) || input.peek(syn::Token![:]) {
+            let prefix = input.parse::<proc_macro2::Punct>()?;
+            name.push(prefix.as_char());
+            written.extend(Some(proc_macro2::TokenTree::from(prefix)));
+            is_dashed = false;
+        }
+
+        loop {
+            let ident = syn::Ident::parse_any(input)?;
+            name.push_str(&ident.unraw().to_string());
+            ident.to_tokens(&mut written);
+            idents.push_value(ident);
+
+            let (separator, separator_tokens) = match Self::parse_separator(input) {
+                Some(separator) => separator,
+                None => break,
+            };
+            is_dashed &= separator == "-";
+            name.push_str(&separator);
+            idents.push_punct(syn::Token![-](separator_tokens.span()));
+            written.extend(separator_tokens);
+        }
+
+        if is_dashed {
+            Ok(Self::Dashed(idents))
+        } else {
+            Ok(Self::Verbatim(name, written))
+        }
+    }
+}
+
+/// A `class:name` or `style:property` directive, merged into the `class` or `style` attribute
+pub enum Directive {
+    Class(String),
+    Style(String),
+}
+
+impl Directive {
+    /// The attribute the directive is merged into
+    pub fn attribute_name(&self) -> &'static str {
+        match self {
+            Self::Class(_) => "class",
+            Self::Style(_) => "style",
+        }
+    }
+}
 
 pub enum ElementAttribute {
     Punned(AttributeKey),
-    WithValueLit(AttributeKey, syn::LitStr),
+    WithValueLit(AttributeKey, syn::Lit),
     WithValue(AttributeKey, syn::Block),
     WithValueOpt(AttributeKey, syn::Block),
     WithValueBool(AttributeKey, syn::Block),
@@ -26,7 +158,38 @@ impl ElementAttribute {
     }
 
     pub fn idents(&self) -> Vec<&syn::Ident> {
-        self.ident().iter().collect::<Vec<_>>()
+        self.ident().idents()
+    }
+
+    /// The HTML attribute name, with raw identifiers unwrapped
+    pub fn name(&self) -> String {
+        self.ident().name()
+    }
+
+    /// The value of an attribute written as a string literal, like `type="text"`
+    /// or `type={"text"}`
+    pub fn literal_value(&self) -> Option<String> {
+        match self {
+            Self::WithValueLit(_, syn::Lit::Str(value)) => Some(value.value()),
+            Self::WithValueLit(_, syn::Lit::Int(value)) => Some(value.base10_digits().to_string()),
+            Self::WithValueLit(_, syn::Lit::Float(value)) => {
+                Some(value.base10_digits().to_string())
+            }
+            // Only boolean attributes use presence, see `presence`
+            Self::WithValueLit(_, syn::Lit::Bool(value))
+                if !html_spec::is_boolean_attribute(&self.name()) =>
+            {
+                Some(value.value.to_string())
+            }
+            Self::WithValue(_, block) => match block.stmts.as_slice() {
+                [syn::Stmt::Expr(syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }))] => Some(value.value()),
+                _ => None,
+            },
+            _ => None,
+        }
     }
 
     pub fn value_tokens(&self) -> proc_macro2::TokenStream {
@@ -48,25 +211,55 @@ impl ElementAttribute {
         }
     }
 
-    pub fn is_optional(&self) -> bool {
-        match self {
-            Self::WithValueOpt(_, _) => true,
-            _ => false,
+    pub fn directive(&self) -> Option<Directive> {
+        let name = self.name();
+        if let Some(class) = name
+            .strip_prefix("class:")
+            .filter(|class| !class.is_empty())
+        {
+            Some(Directive::Class(class.to_string()))
+        } else {
+            name.strip_prefix("style:")
+                .filter(|property| !property.is_empty())
+                .map(|property| Directive::Style(property.to_string()))
         }
     }
 
-    pub fn is_boolean(&self) -> bool {
+    /// The value of a boolean attribute written as a literal, like `hidden=true`,
+    /// or bare, like `hidden`. Other attributes written bare are variables.
+    pub fn literal_bool(&self) -> Option<bool> {
         match self {
-            Self::WithValueBool(_, _) => true,
-            _ => false,
+            Self::WithValueLit(_, syn::Lit::Bool(value)) => Some(value.value),
+            Self::Punned(AttributeKey::Dashed(idents))
+                if idents.len() == 1 && html_spec::is_boolean_attribute(&self.name()) =>
+            {
+                Some(true)
+            }
+            _ => None,
         }
     }
 
-    pub fn validate(self, is_custom_element: bool) -> Result<Self> {
+    /// Whether a boolean attribute like `disabled` is written, for `disabled=true`,
+    /// `disabled=false` and bare `disabled`. Other attributes write `true` and
+    /// `false` as they are, like `aria-hidden="true"`.
+    pub fn presence(&self) -> Option<bool> {
+        self.literal_bool()
+            .filter(|_| html_spec::is_boolean_attribute(&self.name()))
+    }
+
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::WithValueOpt(_, _))
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, Self::WithValueBool(_, _))
+    }
+
+    pub fn validate(self, element_name: &TagName, is_custom_element: bool) -> Result<Self> {
         if is_custom_element {
             self.validate_for_custom_element()
         } else {
-            self.validate_for_simple_element()
+            self.validate_for_simple_element(element_name)
         }
     }
 
@@ -74,17 +267,22 @@ impl ElementAttribute {
         if self.is_optional() {
             let error_message =
                 "Cannot use optional value syntax on custom components. Try to remove `?`";
-            return Err(syn::Error::new(self.ident().span(), error_message));
+            return Err(syn::Error::new_spanned(self.ident(), error_message));
         }
 
         if self.is_boolean() {
             let error_message =
                 "Cannot use boolean value syntax on custom components. Try to remove `!`";
-            return Err(syn::Error::new(self.ident().span(), error_message));
+            return Err(syn::Error::new_spanned(self.ident(), error_message));
         }
 
-
-        if self.idents().len() < 2 {
+        if !matches!(self.ident(), AttributeKey::Dashed(_)) {
+            let error_message = format!(
+                "Can't use `{}` on custom components, which only accept Rust identifiers as props",
+                self.name()
+            );
+            Err(syn::Error::new_spanned(self.ident(), error_message))
+        } else if self.idents().len() < 2 {
             Ok(self)
         } else {
             let alternative_name = self
@@ -99,26 +297,41 @@ impl ElementAttribute {
                 alternative_name
             );
 
-            Err(syn::Error::new(self.ident().span(), error_message))
+            Err(syn::Error::new_spanned(self.ident(), error_message))
         }
     }
 
-    pub fn validate_for_simple_element(self) -> Result<Self> {
+    pub fn validate_for_simple_element(self, element_name: &TagName) -> Result<Self> {
         match (&self, self.idents().len()) {
-            (Self::Punned(ref key), len) if len > 1 => {
-                let error_message = "Can't use punning with dash-delimited values";
-                Err(syn::Error::new(key.span(), error_message))
+            (Self::Punned(ref key), len) if len != 1 => {
+                let error_message = "Can't use punning with dash-delimited or namespaced names";
+                Err(syn::Error::new_spanned(key, error_message))
+            }
+            // String literal keys are an escape hatch for names that can't be validated
+            // against the element, but still have to be writable as attribute names
+            _ if matches!(self.ident(), AttributeKey::Lit(_)) => {
+                if html_spec::is_valid_attribute_name(&self.name()) {
+                    Ok(self)
+                } else {
+                    let error_message = "Attribute names can't be empty, or have spaces, quotes, `>`, `/`, `=` or control characters";
+                    Err(syn::Error::new_spanned(self.ident(), error_message))
+                }
+            }
+            _ if self.directive().is_some() => Ok(self),
+            _ => {
+                let tag_name = element_name.as_string();
+                html_spec::validate_attribute(&tag_name, &self.name()).map_err(
+                    |error_message| syn::Error::new_spanned(self.ident(), error_message),
+                )?;
+                Ok(self)
             }
-            _ => Ok(self),
         }
     }
 }
 
 impl PartialEq for ElementAttribute {
     fn eq(&self, other: &Self) -> bool {
-        let self_idents: Vec<_> = self.ident().iter().collect();
-        let other_idents: Vec<_> = other.ident().iter().collect();
-        self_idents == other_idents
+        self.name() == other.name()
     }
 }
 
@@ -126,14 +339,13 @@ impl Eq for ElementAttribute {}
 
 impl Hash for ElementAttribute {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        let ident = self.idents();
-        Hash::hash(&ident, state)
+        Hash::hash(&self.name(), state)
     }
 }
 
 impl Parse for ElementAttribute {
     fn parse(input: ParseStream) -> Result<Self> {
-        let name = AttributeKey::parse_separated_nonempty_with(input, syn::Ident::parse_any)?;
+        let name = input.parse::<AttributeKey>()?;
         let not_punned = input.peek(syn::Token![=]);
 
         if !not_punned {
@@ -144,19 +356,42 @@ impl Parse for ElementAttribute {
         let value = if input.peek(syn::token::Brace) {
             input.parse::<syn::Block>()?
         } else {
-            let v = input.parse::<syn::LitStr>()?;
-            return Ok(Self::WithValueLit(name, v));
+            return Ok(Self::WithValueLit(name, parse_literal_value(input)?));
         };
 
         if input.peek(syn::Token![?]) {
             input.parse::<syn::Token![?]>().unwrap();
             Ok(Self::WithValueOpt(name, value))
-        } else if input.peek(syn::Token![!])  {
+        } else if input.peek(syn::Token![!]) {
             input.parse::<syn::Token![!]>().unwrap();
             Ok(Self::WithValueBool(name, value))
-        }
-        else {
+        } else {
             Ok(Self::WithValue(name, value))
         }
     }
 }
+
+/// Parses a string, number or boolean literal value, like `"text"`, `2`, `-1` or `true`
+fn parse_literal_value(input: ParseStream) -> Result<syn::Lit> {
+    let minus = input.parse::<Option<syn::Token![-]>>()?;
+    let value = input.parse::<syn::Lit>()?;
+
+    match (minus, value) {
+        (None, value @ syn::Lit::Str(_))
+        | (None, value @ syn::Lit::Bool(_))
+        | (None, value @ syn::Lit::Int(_))
+        | (None, value @ syn::Lit::Float(_)) => Ok(value),
+        (Some(minus), syn::Lit::Int(value)) => Ok(syn::Lit::Int(syn::LitInt::new(
+            &format!("-{}", value),
+            minus.span,
+        ))),
+        (Some(minus), syn::Lit::Float(value)) => Ok(syn::Lit::Float(syn::LitFloat::new(
+            &format!("-{}", value),
+            minus.span,
+        ))),
+        (_, value) => Err(syn::Error::new_spanned(
+            value,
+            "Expected a string, number or boolean literal, or a `{block}`",
+        )),
+    }
+}
@@ -1,14 +1,16 @@
 use crate::children::Children;
-use crate::element_attribute::ElementAttribute;
-use crate::tags::FallbackAttributes;
+use crate::element_attribute::{Directive, ElementAttribute};
+use crate::html_spec;
+use crate::tags::{FallbackAttributes, TagName};
+use proc_macro2::Span;
 use proc_macro_error::emit_error;
-use quote::{quote, ToTokens};
-use std::collections::HashSet;
+use quote::{quote, quote_spanned, ToTokens};
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream, Result};
 use syn::spanned::Spanned;
 
-pub type Attributes = HashSet<ElementAttribute>;
+/// The attributes of an element, in source order
+pub type Attributes = Vec<ElementAttribute>;
 
 #[derive(Default)]
 pub struct ElementAttributes {
@@ -22,35 +24,62 @@ impl ElementAttributes {
 
     pub fn for_custom_element<'f, 'c>(
         &self,
+        span: Span,
         fallback_attributes: Option<&'f FallbackAttributes>,
         children: &'c Children,
     ) -> CustomElementAttributes<'_, 'f, 'c> {
         CustomElementAttributes {
+            span,
             attributes: &self.attributes,
             fallback_attributes,
             children,
         }
     }
 
-    pub fn for_simple_element(&self) -> SimpleElementAttributes<'_> {
+    pub fn for_simple_element<'f>(
+        &self,
+        fallback_attributes: Option<&'f FallbackAttributes>,
+    ) -> SimpleElementAttributes<'_, 'f> {
         SimpleElementAttributes {
             attributes: &self.attributes,
+            spread: fallback_attributes.and_then(FallbackAttributes::spread_expr),
         }
     }
 
-    pub fn parse(input: ParseStream, is_custom_element: bool) -> Result<Self> {
+    /// Adds the attributes of `other`, reporting the ones already defined
+    pub fn extend(&mut self, other: ElementAttributes) {
+        for attribute in other.attributes {
+            if self.attributes.contains(&attribute) {
+                emit_error!(
+                    attribute.ident(),
+                    "There is a previous definition of the {} attribute",
+                    attribute.name()
+                );
+            } else {
+                self.attributes.push(attribute);
+            }
+        }
+    }
+
+    pub fn parse(
+        input: ParseStream,
+        element_name: &TagName,
+        is_custom_element: bool,
+    ) -> Result<Self> {
         let mut parsed_self = input.parse::<Self>()?;
 
         let new_attributes: Attributes = parsed_self
             .attributes
-            .drain()
-            .filter_map(|attribute| match attribute.validate(is_custom_element) {
-                Ok(x) => Some(x),
-                Err(err) => {
-                    emit_error!(err.span(), "Invalid attribute: {}", err);
-                    None
-                }
-            })
+            .drain(..)
+            .filter_map(
+                |attribute| match attribute.validate(element_name, is_custom_element) {
+                    Ok(x) => Some(x),
+                    Err(err) => {
+                        emit_error!(err.span(), "Invalid attribute: {}", err);
+                        None
+                    }
+                },
+            )
             .collect();
 
         Ok(ElementAttributes::new(new_attributes))
@@ -59,30 +88,30 @@ impl ElementAttributes {
 
 impl Parse for ElementAttributes {
     fn parse(input: ParseStream) -> Result<Self> {
-        let mut attributes: HashSet<ElementAttribute> = HashSet::new();
-        while input.peek(syn::Ident::peek_any) {
-            let attribute = input.parse::<ElementAttribute>()?;
-            let ident = attribute.ident();
-            if attributes.contains(&attribute) {
-                emit_error!(
-                    ident.span(),
-                    "There is a previous definition of the {} attribute",
-                    quote!(#ident)
-                );
-            }
-            attributes.insert(attribute);
+        let mut attributes: Attributes = Vec::new();
+        while input.peek(syn::Ident::peek_any)
+            || input.peek(syn::LitStr)
+            || input.peek(syn::Token![@])
+            || (input.peek(syn::Token![:]) && !input.peek(syn::Token![::]))
+        {
+            attributes.push(input.parse::<ElementAttribute>()?);
         }
-        Ok(ElementAttributes::new(attributes))
+
+        let mut element_attributes = ElementAttributes::default();
+        element_attributes.extend(ElementAttributes::new(attributes));
+        Ok(element_attributes)
     }
 }
 
 pub struct CustomElementAttributes<'a, 'f, 'c> {
+    /// The span of the component name, given to the struct literal braces
+    span: Span,
     attributes: &'a Attributes,
     fallback_attributes: Option<&'f FallbackAttributes>,
     children: &'c Children,
 }
 
-impl<'a, 'f, 'c> ToTokens for CustomElementAttributes<'a,'f, 'c> {
+impl<'a, 'f, 'c> ToTokens for CustomElementAttributes<'a, 'f, 'c> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let mut attrs: Vec<_> = self
             .attributes
@@ -91,96 +120,216 @@ impl<'a, 'f, 'c> ToTokens for CustomElementAttributes<'a,'f, 'c> {
                 let ident = attribute.ident();
                 let value = attribute.value_tokens();
 
-                quote! {
+                quote_spanned! {ident.span()=>
                     #ident: #value
                 }
             })
             .collect();
 
         if self.children.len() > 0 {
-            let children_tuple = self.children.as_option_of_tuples_tokens();
+            let children = self.children.as_option_tokens();
             attrs.push(quote! {
-                children: #children_tuple
+                children: #children
             });
         }
 
-        let quoted = if attrs.len() == 0 && !self.fallback_attributes.is_some() {
+        let span = self.span;
+        let quoted = if attrs.is_empty() && self.fallback_attributes.is_none() {
             quote!()
-        } else if let Some(FallbackAttributes(block)) = self.fallback_attributes{
+        } else if let Some(FallbackAttributes(block)) = self.fallback_attributes {
             let inner = &block.stmts[0];
-            if attrs.len() > 0 {
-                quote!({ #(#attrs),*,  #inner})
-            }
-            else {
-                quote!({ #inner})
+            if !attrs.is_empty() {
+                quote_spanned!(span=> { #(#attrs),*,  #inner})
+            } else {
+                quote_spanned!(span=> { #inner})
             }
-        }
-        else {
-            quote!({ #(#attrs),* })
-        }
-        ;
-
+        } else {
+            quote_spanned!(span=> { #(#attrs),* })
+        };
 
         quoted.to_tokens(tokens);
     }
 }
 
-pub struct SimpleElementAttributes<'a> {
+pub struct SimpleElementAttributes<'a, 'f> {
     attributes: &'a Attributes,
+    /// The collection of name and value pairs spread with `{..attributes}`
+    spread: Option<&'f syn::Expr>,
 }
 
-impl<'a> ToTokens for SimpleElementAttributes<'a> {
+impl<'a, 'f> ToTokens for SimpleElementAttributes<'a, 'f> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        if self.attributes.is_empty() {
+        if self.attributes.is_empty() && self.spread.is_none() {
             quote!(None).to_tokens(tokens);
         } else {
+            let merged_names: Vec<_> = self
+                .attributes
+                .iter()
+                .filter_map(|attribute| attribute.directive())
+                .map(|directive| directive.attribute_name())
+                .collect();
+
+            // Attributes are inserted in source order, like in statically rendered
+            // markup. Merged ones take the place of their first part.
+            let has_spread = self.spread.is_some();
+            let mut merged_written: Vec<&str> = Vec::new();
             let attrs: Vec<_> = self
                 .attributes
                 .iter()
-                .map(|attribute| {
-                    let mut iter = attribute.ident().iter();
-                    let first_word = iter.next().unwrap().unraw();
-                    let ident = iter.fold(first_word.to_string(), |acc, curr| {
-                        format!("{}-{}", acc, curr.unraw())
-                    });
-                    let value = attribute.value_tokens();
-
-                    if attribute.is_optional() {
-                        let mut iter = attribute.ident().iter();
-                        let first_word = iter.next().unwrap().unraw();
-                        let ident_underscored = iter.fold(first_word.to_string(), |acc, curr| {
-                            format!("{}_{}", acc, curr.unraw())
-                        });
-                        let attr_ident = syn::Ident::new(&ident_underscored, attribute.ident().span());
-
-                        quote! {
-                            if let ::std::option::Option::Some(#attr_ident) = #value {
-                                hm.insert(#ident, ::std::borrow::Cow::from(#attr_ident));
-                            }
-                        }
-                    }
-                    else if attribute.is_boolean() {
-                        quote! {
-                            if #value {
-                                hm.insert(#ident, std::borrow::Cow::from(""));
-                            }
-                        }
-                    }
-                    else {
-                        quote! {
-                            hm.insert(#ident, ::std::borrow::Cow::from(#value));
+                .filter_map(|attribute| {
+                    let name = match attribute.directive() {
+                        Some(directive) => directive.attribute_name().to_string(),
+                        None => attribute.name(),
+                    };
+                    match merged_names.iter().find(|merged| **merged == name) {
+                        Some(merged) if merged_written.contains(merged) => None,
+                        Some(merged) => {
+                            merged_written.push(merged);
+                            Some(merged_attribute_tokens(self.attributes, merged, has_spread))
                         }
+                        None => Some(attribute_tokens(attribute, has_spread)),
                     }
                 })
                 .collect();
 
-            let hashmap_declaration = quote! {{
-                let mut hm = std::collections::HashMap::<&str, ::std::borrow::Cow<'_, str>>::new();
+            // Spread attributes are inserted first, so explicit ones override them
+            let spread = self.spread.map(|spread| {
+                let name = syn::Ident::new("name", Span::mixed_site());
+                let value = syn::Ident::new("value", Span::mixed_site());
+                quote! {
+                    for (#name, #value) in #spread {
+                        let #name: ::std::borrow::Cow<'_, str> = ::std::convert::Into::into(#name);
+                        if let ::std::option::Option::Some(#value) =
+                            tide_jsx::spread_attribute_value(&#name, #value)
+                        {
+                            hm.insert(#name, #value);
+                        }
+                    }
+                }
+            });
+
+            let map_declaration = quote! {{
+                let mut hm = tide_jsx::AttributeMap::new();
+                #spread
                 #(#attrs)*
                 Some(hm)
             }};
 
-            hashmap_declaration.to_tokens(tokens);
+            map_declaration.to_tokens(tokens);
         }
     }
 }
+
+/// Inserts a plain attribute into the attribute map, if its value is written.
+/// With spread attributes, an absent value removes the spread one, so explicit
+/// attributes always override them.
+fn attribute_tokens(attribute: &ElementAttribute, has_spread: bool) -> proc_macro2::TokenStream {
+    let name = attribute.name();
+    let ident = quote!(::std::borrow::Cow::Borrowed(#name));
+    let absent = if has_spread {
+        quote! { hm.shift_remove(#name); }
+    } else {
+        quote!()
+    };
+    let otherwise = if has_spread {
+        quote! { else { #absent } }
+    } else {
+        quote!()
+    };
+    let value = attribute.value_tokens();
+    let binding = syn::Ident::new("value", Span::mixed_site());
+    // Dynamic values decide whether the attribute is written, with `true` and
+    // `false` meaning presence on boolean attributes only
+    let into_value = if html_spec::is_boolean_attribute(&name) {
+        quote!(tide_jsx::AttributeValue::into_boolean_attribute_value)
+    } else {
+        quote!(tide_jsx::AttributeValue::into_attribute_value)
+    };
+    let insert = |value: proc_macro2::TokenStream| {
+        quote! {
+            if let ::std::option::Option::Some(#binding) = #into_value(#value) {
+                hm.insert(#ident, #binding);
+            } #otherwise
+        }
+    };
+
+    if let Some(present) = attribute.presence() {
+        if present {
+            quote! {
+                hm.insert(#ident, ::std::borrow::Cow::from(""));
+            }
+        } else {
+            absent
+        }
+    } else if let Some(value) = attribute.literal_value() {
+        quote! {
+            hm.insert(#ident, ::std::borrow::Cow::from(#value));
+        }
+    } else if attribute.is_optional() {
+        let inserted = insert(quote!(#binding));
+        quote! {
+            if let ::std::option::Option::Some(#binding) = #value {
+                #inserted
+            } #otherwise
+        }
+    } else if attribute.is_boolean() {
+        quote! {
+            if #value {
+                hm.insert(#ident, ::std::borrow::Cow::from(""));
+            } #otherwise
+        }
+    } else {
+        insert(value)
+    }
+}
+
+/// Merges a `class` or `style` attribute with its directives, in source order
+fn merged_attribute_tokens(
+    attributes: &Attributes,
+    name: &str,
+    has_spread: bool,
+) -> proc_macro2::TokenStream {
+    let list = syn::Ident::new("list", Span::mixed_site());
+    let value = syn::Ident::new("value", Span::mixed_site());
+
+    let parts = attributes.iter().filter_map(|attribute| {
+        let attribute_value = attribute.value_tokens();
+        match attribute.directive() {
+            Some(Directive::Class(class)) if name == "class" => {
+                Some(quote! { #list.push_if(#class, #attribute_value); })
+            }
+            Some(Directive::Style(property)) if name == "style" => {
+                Some(quote! { #list.set(#property, #attribute_value); })
+            }
+            None if attribute.name() == name => Some(quote! {
+                if let ::std::option::Option::Some(#value) =
+                    tide_jsx::AttributeValue::into_attribute_value(#attribute_value)
+                {
+                    #list.push(&#value);
+                }
+            }),
+            _ => None,
+        }
+    });
+
+    let list_type = match name {
+        "class" => quote!(tide_jsx::ClassList),
+        _ => quote!(tide_jsx::StyleList),
+    };
+
+    let otherwise = if has_spread {
+        quote! { else { hm.shift_remove(#name); } }
+    } else {
+        quote!()
+    };
+
+    quote! {{
+        let mut #list = #list_type::new();
+        #(#parts)*
+        if let ::std::option::Option::Some(#value) =
+            tide_jsx::AttributeValue::into_attribute_value(#list)
+        {
+            hm.insert(::std::borrow::Cow::Borrowed(#name), #value);
+        } #otherwise
+    }}
+}
@@ -0,0 +1,57 @@
+use crate::html_entities::validate_character_reference;
+use proc_macro_error::emit_error;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Result};
+
+/// A character reference like `&nbsp;`, `&#8212;` or `&#x2014;`, checked against
+/// the HTML standard and written verbatim
+pub struct Entity {
+    reference: String,
+}
+
+impl Entity {
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(syn::Token![&])
+    }
+
+    pub fn static_html(&self) -> Option<String> {
+        Some(format!("&{};", self.reference))
+    }
+}
+
+impl Parse for Entity {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ampersand = input.parse::<syn::Token![&]>()?;
+
+        let (reference, span) = if input.peek(syn::Token![#]) {
+            input.parse::<syn::Token![#]>()?;
+            let (number, span) = if input.peek(syn::LitInt) {
+                let number = input.parse::<syn::LitInt>()?;
+                (number.to_string(), number.span())
+            } else {
+                let number = input.call(syn::Ident::parse_any)?;
+                (number.to_string(), number.span())
+            };
+            (format!("#{}", number), span)
+        } else {
+            let name = input.call(syn::Ident::parse_any)?;
+            (name.to_string(), name.span())
+        };
+        let semicolon = input.parse::<syn::Token![;]>()?;
+
+        if let Err(error_message) = validate_character_reference(&reference) {
+            let span = ampersand.span.join(semicolon.span).unwrap_or(span);
+            emit_error!(span, "{}", error_message);
+        }
+
+        Ok(Self { reference })
+    }
+}
+
+impl ToTokens for Entity {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let html = format!("&{};", self.reference);
+        quote! { tide_jsx::Raw::from(#html) }.to_tokens(tokens);
+    }
+}
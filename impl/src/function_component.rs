@@ -1,16 +1,41 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
 use proc_macro_error::emit_error;
 use quote::quote;
 use syn::spanned::Spanned;
 
-pub fn create_function_component(f: syn::ItemFn) -> TokenStream {
+/// How a `#[component]` is compiled
+pub enum ComponentKind {
+    /// The render body is monomorphized for every set of generic arguments
+    Generic,
+    /// `Render`-bounded type parameters, like `Children: Render`, are erased to
+    /// `tide_jsx::Children` in the render body, so it is compiled once
+    TypeErased,
+}
+
+impl syn::parse::Parse for ComponentKind {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            Ok(Self::Generic)
+        } else {
+            input.parse::<syn::Token![dyn]>()?;
+            Ok(Self::TypeErased)
+        }
+    }
+}
+
+pub fn create_function_component(f: syn::ItemFn, kind: ComponentKind) -> TokenStream {
+    if let ComponentKind::TypeErased = kind {
+        return create_type_erased_component(f);
+    }
+
     let struct_name = f.sig.ident;
     let (impl_generics, ty_generics, where_clause) = f.sig.generics.split_for_impl();
     let inputs = f.sig.inputs;
     let block = f.block;
     let vis = f.vis;
 
-    let inputs_block = if inputs.len() > 0 {
+    let inputs_block = if !inputs.is_empty() {
         let input_names: Vec<_> = inputs.iter().collect();
 
         quote!({ #(#vis #input_names),* })
@@ -18,7 +43,7 @@ pub fn create_function_component(f: syn::ItemFn) -> TokenStream {
         quote!(;)
     };
 
-    let inputs_reading = if inputs.len() == 0 {
+    let inputs_reading = if inputs.is_empty() {
         quote!()
     } else {
         let input_names: Vec<_> = inputs
@@ -55,3 +80,129 @@ pub fn create_function_component(f: syn::ItemFn) -> TokenStream {
         }
     })
 }
+
+/// Whether a generic parameter is bounded by `Render`
+fn is_render_bounded(param: &syn::TypeParam) -> bool {
+    param.bounds.iter().any(|bound| match bound {
+        syn::TypeParamBound::Trait(bound) => bound
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Render")
+            .unwrap_or(false),
+        syn::TypeParamBound::Lifetime(_) => false,
+    })
+}
+
+fn is_type_param(ty: &syn::Type, ident: &syn::Ident) -> bool {
+    match ty {
+        syn::Type::Path(path) => path.qself.is_none() && path.path.is_ident(ident),
+        _ => false,
+    }
+}
+
+fn mentions_ident(tokens: proc_macro2::TokenStream, ident: &syn::Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(token) => token == *ident,
+        TokenTree::Group(group) => mentions_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+fn create_type_erased_component(f: syn::ItemFn) -> TokenStream {
+    let struct_name = f.sig.ident;
+    let (impl_generics, ty_generics, where_clause) = f.sig.generics.split_for_impl();
+    let block = f.block;
+    let vis = f.vis;
+
+    if let Some(where_clause) = where_clause {
+        emit_error!(
+            where_clause.span(),
+            "`#[component(dyn)]` doesn't support where clauses. Move the bounds to the generic parameters"
+        );
+    }
+
+    let inputs: Vec<_> = f
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|argument| match argument {
+            syn::FnArg::Typed(typed) => Some(typed),
+            syn::FnArg::Receiver(rec) => {
+                emit_error!(rec.span(), "Don't use `self` on components");
+                None
+            }
+        })
+        .collect();
+
+    // A type parameter can only be erased when it is the whole type of an input
+    let erased_params: Vec<_> = f
+        .sig
+        .generics
+        .type_params()
+        .filter(|param| is_render_bounded(param))
+        .map(|param| &param.ident)
+        .filter(|ident| {
+            let mut mentioning = inputs
+                .iter()
+                .filter(|input| mentions_ident(quote!(#input), ident));
+            match (mentioning.next(), mentioning.next()) {
+                (Some(input), None) => is_type_param(&input.ty, ident),
+                _ => false,
+            }
+        })
+        .collect();
+
+    if erased_params.is_empty() {
+        emit_error!(
+            struct_name.span(),
+            "`#[component(dyn)]` has nothing to erase. Declare children as a generic parameter bounded by `Render`, like `children: Children` with `Children: Render`"
+        );
+    }
+
+    let mut body_generics = f.sig.generics.clone();
+    body_generics.params = body_generics
+        .params
+        .into_iter()
+        .filter(|param| match param {
+            syn::GenericParam::Type(param) => !erased_params.contains(&&param.ident),
+            _ => true,
+        })
+        .collect();
+
+    let mut body_params = vec![];
+    let mut body_args = vec![];
+    for input in &inputs {
+        let pat = &input.pat;
+        let is_erased = erased_params
+            .iter()
+            .any(|ident| mentions_ident(quote!(#input), ident));
+
+        if is_erased {
+            body_params.push(quote!(#pat: tide_jsx::Children<'_>));
+            body_args.push(quote!(tide_jsx::Children::new(&self.#pat)));
+        } else {
+            let ty = &input.ty;
+            body_params.push(quote!(#pat: &#ty));
+            body_args.push(quote!(&self.#pat));
+        }
+    }
+
+    TokenStream::from(quote! {
+        #[derive(Debug, Clone)]
+        #vis struct #struct_name #impl_generics { #(#vis #inputs),* }
+
+        impl #impl_generics tide_jsx::Render for #struct_name #ty_generics #where_clause {
+            fn render_into(&self, w: &mut String) -> std::fmt::Result {
+                // Not generic over the erased parameters, so it is only compiled once
+                #[allow(non_snake_case)]
+                fn render_body #body_generics (#(#body_params,)* w: &mut String) -> std::fmt::Result {
+                    let result = #block;
+                    tide_jsx::Render::render_into(&result, w)
+                }
+
+                render_body(#(#body_args,)* w)
+            }
+        }
+    })
+}
@@ -0,0 +1,31 @@
+/// Escapes text the same way the runtime crate does, for markup rendered at compile time
+pub fn escape_html(html: &str) -> String {
+    let mut escaped = String::with_capacity(html.len());
+    for c in html.chars() {
+        match c {
+            '>' => escaped.push_str("&gt;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            '&' => escaped.push_str("&amp;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        };
+    }
+
+    escaped
+}
+
+/// Escapes the text of an HTML comment the same way the runtime crate does
+pub fn escape_comment(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut previous = None;
+    for c in text.chars() {
+        if c == '-' && previous == Some('-') {
+            escaped.push(' ');
+        }
+        escaped.push(c);
+        previous = Some(c);
+    }
+
+    escaped
+}
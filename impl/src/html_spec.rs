@@ -0,0 +1,868 @@
+//! Knowledge about HTML elements and attributes, used to validate `rsx!` markup
+//! at compile time.
+
+/// Elements whose text is rendered with its whitespace as written
+pub const PRESERVED_WHITESPACE_ELEMENTS: &[&str] = &["pre", "textarea"];
+
+pub fn preserves_whitespace(tag_name: &str) -> bool {
+    PRESERVED_WHITESPACE_ELEMENTS.contains(&tag_name)
+}
+
+/// Attributes that can be set on every HTML element
+const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "accesskey",
+    "autocapitalize",
+    "autocorrect",
+    "autofocus",
+    "class",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "enterkeyhint",
+    "exportparts",
+    "headingoffset",
+    "headingreset",
+    "hidden",
+    "id",
+    "inert",
+    "inputmode",
+    "is",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "lang",
+    "nonce",
+    "part",
+    "popover",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+    "virtualkeyboardpolicy",
+    "writingsuggestions",
+];
+
+/// Event handler content attributes that can be set on every HTML element
+const GLOBAL_EVENT_HANDLERS: &[&str] = &[
+    "onabort",
+    "onanimationcancel",
+    "onanimationend",
+    "onanimationiteration",
+    "onanimationstart",
+    "onauxclick",
+    "onbeforeinput",
+    "onbeforematch",
+    "onbeforetoggle",
+    "onblur",
+    "oncancel",
+    "oncanplay",
+    "oncanplaythrough",
+    "onchange",
+    "onclick",
+    "onclose",
+    "oncommand",
+    "oncontextlost",
+    "oncontextmenu",
+    "oncontextrestored",
+    "oncopy",
+    "oncuechange",
+    "oncut",
+    "ondblclick",
+    "ondrag",
+    "ondragend",
+    "ondragenter",
+    "ondragleave",
+    "ondragover",
+    "ondragstart",
+    "ondrop",
+    "ondurationchange",
+    "onemptied",
+    "onended",
+    "onerror",
+    "onfocus",
+    "onfocusin",
+    "onfocusout",
+    "onformdata",
+    "onfullscreenchange",
+    "onfullscreenerror",
+    "ongotpointercapture",
+    "oninput",
+    "oninvalid",
+    "onkeydown",
+    "onkeypress",
+    "onkeyup",
+    "onload",
+    "onloadeddata",
+    "onloadedmetadata",
+    "onloadstart",
+    "onlostpointercapture",
+    "onmousedown",
+    "onmouseenter",
+    "onmouseleave",
+    "onmousemove",
+    "onmouseout",
+    "onmouseover",
+    "onmouseup",
+    "onpaste",
+    "onpause",
+    "onplay",
+    "onplaying",
+    "onpointercancel",
+    "onpointerdown",
+    "onpointerenter",
+    "onpointerleave",
+    "onpointermove",
+    "onpointerout",
+    "onpointerover",
+    "onpointerrawupdate",
+    "onpointerup",
+    "onprogress",
+    "onratechange",
+    "onreset",
+    "onresize",
+    "onscroll",
+    "onscrollend",
+    "onscrollsnapchange",
+    "onscrollsnapchanging",
+    "onsecuritypolicyviolation",
+    "onseeked",
+    "onseeking",
+    "onselect",
+    "onselectionchange",
+    "onselectstart",
+    "onslotchange",
+    "onstalled",
+    "onsubmit",
+    "onsuspend",
+    "ontimeupdate",
+    "ontoggle",
+    "ontouchcancel",
+    "ontouchend",
+    "ontouchmove",
+    "ontouchstart",
+    "ontransitioncancel",
+    "ontransitionend",
+    "ontransitionrun",
+    "ontransitionstart",
+    "onvolumechange",
+    "onwaiting",
+    "onwheel",
+];
+
+/// Event handlers of the `Window` object, exposed on `<body>`
+const WINDOW_EVENT_HANDLERS: &[&str] = &[
+    "onafterprint",
+    "onbeforeprint",
+    "onbeforeunload",
+    "onhashchange",
+    "onlanguagechange",
+    "onmessage",
+    "onmessageerror",
+    "onoffline",
+    "ononline",
+    "onpagehide",
+    "onpagereveal",
+    "onpageshow",
+    "onpageswap",
+    "onpopstate",
+    "onrejectionhandled",
+    "onstorage",
+    "onunhandledrejection",
+    "onunload",
+];
+
+/// Attribute name prefixes that are valid on every element, including the XML
+/// namespaces that can be used in HTML documents
+const ALLOWED_PREFIXES: &[&str] = &["data-", "aria-", "xml:", "xmlns", "xlink:"];
+
+/// Framework attributes allowed by default, including the `@event` and `:binding`
+/// shorthands of Alpine.js and Vue. More can be added with the
+/// `TIDE_JSX_ALLOWED_ATTRIBUTES` environment variable.
+const DEFAULT_ALLOW_LIST: &[&str] = &["hx-*", "x-*", "@*", ":*"];
+
+/// The environment variable holding a comma-separated allow-list of extra
+/// attribute names. Entries ending with `*` match every attribute with that prefix.
+pub const ALLOW_LIST_ENV: &str = "TIDE_JSX_ALLOWED_ATTRIBUTES";
+
+/// The allow list of `ALLOW_LIST_ENV` when the macros were compiled. Reading it
+/// with `option_env!` lets Cargo track the variable, so changing it rebuilds the
+/// macros and the code using them.
+const CONFIGURED_ALLOW_LIST: Option<&str> = option_env!("TIDE_JSX_ALLOWED_ATTRIBUTES");
+
+/// Attributes whose presence means `true`, written bare like `<input disabled />`
+pub const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "disablepictureinpicture",
+    "disableremoteplayback",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+    "shadowrootclonable",
+    "shadowrootcustomelementregistry",
+    "shadowrootdelegatesfocus",
+    "shadowrootserializable",
+    "webkitdirectory",
+];
+
+pub fn is_boolean_attribute(attribute_name: &str) -> bool {
+    BOOLEAN_ATTRIBUTES.contains(&attribute_name)
+}
+
+/// Elements that can't have any contents and are written without a closing tag.
+/// The obsolete `command`, `keygen`, `menuitem` and `param` are no longer void,
+/// following the HTML standard.
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+pub fn is_void_element(tag_name: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag_name)
+}
+
+/// The `aria-*` states and properties defined by WAI-ARIA
+pub const ARIA_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-atomic",
+    "aria-autocomplete",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-busy",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-dropeffect",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-level",
+    "aria-live",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-owns",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-relevant",
+    "aria-required",
+    "aria-roledescription",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+/// The non-abstract roles defined by WAI-ARIA
+pub const ARIA_ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "application",
+    "article",
+    "banner",
+    "blockquote",
+    "button",
+    "caption",
+    "cell",
+    "checkbox",
+    "code",
+    "columnheader",
+    "combobox",
+    "comment",
+    "complementary",
+    "contentinfo",
+    "definition",
+    "deletion",
+    "dialog",
+    "directory",
+    "document",
+    "emphasis",
+    "feed",
+    "figure",
+    "form",
+    "generic",
+    "grid",
+    "gridcell",
+    "group",
+    "heading",
+    "img",
+    "insertion",
+    "link",
+    "list",
+    "listbox",
+    "listitem",
+    "log",
+    "main",
+    "mark",
+    "marquee",
+    "math",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "meter",
+    "navigation",
+    "none",
+    "note",
+    "option",
+    "paragraph",
+    "presentation",
+    "progressbar",
+    "radio",
+    "radiogroup",
+    "region",
+    "row",
+    "rowgroup",
+    "rowheader",
+    "scrollbar",
+    "search",
+    "searchbox",
+    "separator",
+    "slider",
+    "spinbutton",
+    "status",
+    "strong",
+    "subscript",
+    "suggestion",
+    "superscript",
+    "switch",
+    "tab",
+    "table",
+    "tablist",
+    "tabpanel",
+    "term",
+    "textbox",
+    "time",
+    "timer",
+    "toolbar",
+    "tooltip",
+    "tree",
+    "treegrid",
+    "treeitem",
+];
+
+/// Role prefixes of the DPUB-ARIA and Graphics ARIA modules
+pub const ARIA_ROLE_PREFIXES: &[&str] = &["doc-", "graphics-"];
+
+/// The element-specific attributes of a known HTML element, or `None` for elements
+/// that are not validated (custom elements, SVG, MathML...)
+fn element_attributes(tag_name: &str) -> Option<&'static [&'static str]> {
+    let attributes: &[&str] = match tag_name {
+        "a" => &[
+            "download",
+            "href",
+            "hreflang",
+            "ping",
+            "referrerpolicy",
+            "rel",
+            "target",
+            "type",
+        ],
+        "area" => &[
+            "alt",
+            "coords",
+            "download",
+            "href",
+            "ping",
+            "referrerpolicy",
+            "rel",
+            "shape",
+            "target",
+        ],
+        "audio" => &[
+            "autoplay",
+            "controls",
+            "crossorigin",
+            "disableremoteplayback",
+            "loop",
+            "muted",
+            "preload",
+            "src",
+        ],
+        "base" => &["href", "target"],
+        "blockquote" | "q" => &["cite"],
+        "body" => WINDOW_EVENT_HANDLERS,
+        "button" => &[
+            "command",
+            "commandfor",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "name",
+            "popovertarget",
+            "popovertargetaction",
+            "type",
+            "value",
+        ],
+        "canvas" => &["height", "width"],
+        "col" | "colgroup" => &["span"],
+        "data" => &["value"],
+        "del" | "ins" => &["cite", "datetime"],
+        "details" => &["name", "open"],
+        "dialog" => &["closedby", "open"],
+        "embed" => &["height", "src", "type", "width"],
+        "fieldset" => &["disabled", "form", "name"],
+        "form" => &[
+            "accept-charset",
+            "action",
+            "autocomplete",
+            "enctype",
+            "method",
+            "name",
+            "novalidate",
+            "rel",
+            "target",
+        ],
+        "html" => &["manifest", "xmlns"],
+        "iframe" => &[
+            "allow",
+            "allowfullscreen",
+            "height",
+            "loading",
+            "name",
+            "referrerpolicy",
+            "sandbox",
+            "src",
+            "srcdoc",
+            "width",
+        ],
+        "img" => &[
+            "alt",
+            "crossorigin",
+            "decoding",
+            "fetchpriority",
+            "height",
+            "ismap",
+            "loading",
+            "referrerpolicy",
+            "sizes",
+            "src",
+            "srcset",
+            "usemap",
+            "width",
+        ],
+        "input" => &[
+            "accept",
+            "alpha",
+            "alt",
+            "autocomplete",
+            "capture",
+            "checked",
+            "colorspace",
+            "dirname",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "height",
+            "list",
+            "max",
+            "maxlength",
+            "min",
+            "minlength",
+            "multiple",
+            "name",
+            "pattern",
+            "placeholder",
+            "popovertarget",
+            "popovertargetaction",
+            "readonly",
+            "required",
+            "size",
+            "src",
+            "step",
+            "type",
+            "value",
+            "webkitdirectory",
+            "width",
+        ],
+        "label" => &["for"],
+        "li" => &["value"],
+        "link" => &[
+            "as",
+            "blocking",
+            "color",
+            "crossorigin",
+            "disabled",
+            "fetchpriority",
+            "href",
+            "hreflang",
+            "imagesizes",
+            "imagesrcset",
+            "integrity",
+            "media",
+            "referrerpolicy",
+            "rel",
+            "sizes",
+            "type",
+        ],
+        "map" => &["name"],
+        // `property` is RDFa, used by OpenGraph tags like `<meta property="og:title">`
+        "meta" => &[
+            "charset",
+            "content",
+            "http-equiv",
+            "media",
+            "name",
+            "property",
+        ],
+        "meter" => &["high", "low", "max", "min", "optimum", "value"],
+        "object" => &["data", "form", "height", "name", "type", "width"],
+        "ol" => &["reversed", "start", "type"],
+        "optgroup" => &["disabled", "label"],
+        "option" => &["disabled", "label", "selected", "value"],
+        "output" => &["for", "form", "name"],
+        "progress" => &["max", "value"],
+        "script" => &[
+            "async",
+            "blocking",
+            "crossorigin",
+            "defer",
+            "fetchpriority",
+            "integrity",
+            "nomodule",
+            "referrerpolicy",
+            "src",
+            "type",
+        ],
+        "select" => &[
+            "autocomplete",
+            "disabled",
+            "form",
+            "multiple",
+            "name",
+            "required",
+            "size",
+        ],
+        "slot" => &["name"],
+        "source" => &["height", "media", "sizes", "src", "srcset", "type", "width"],
+        "style" => &["blocking", "media"],
+        "td" => &["colspan", "headers", "rowspan"],
+        "template" => &[
+            "shadowrootclonable",
+            "shadowrootcustomelementregistry",
+            "shadowrootdelegatesfocus",
+            "shadowrootmode",
+            "shadowrootserializable",
+        ],
+        "textarea" => &[
+            "autocomplete",
+            "cols",
+            "dirname",
+            "disabled",
+            "form",
+            "maxlength",
+            "minlength",
+            "name",
+            "placeholder",
+            "readonly",
+            "required",
+            "rows",
+            "wrap",
+        ],
+        "th" => &["abbr", "colspan", "headers", "rowspan", "scope"],
+        "time" => &["datetime"],
+        "track" => &["default", "kind", "label", "src", "srclang"],
+        "video" => &[
+            "autoplay",
+            "controls",
+            "crossorigin",
+            "disablepictureinpicture",
+            "disableremoteplayback",
+            "height",
+            "loop",
+            "muted",
+            "playsinline",
+            "poster",
+            "preload",
+            "src",
+            "width",
+        ],
+        "abbr" | "address" | "article" | "aside" | "b" | "bdi" | "bdo" | "br" | "caption"
+        | "cite" | "code" | "datalist" | "dd" | "dfn" | "div" | "dl" | "dt" | "em"
+        | "figcaption" | "figure" | "footer" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "head"
+        | "header" | "hgroup" | "hr" | "i" | "kbd" | "legend" | "main" | "mark" | "menu"
+        | "nav" | "noscript" | "p" | "picture" | "pre" | "rp" | "rt" | "ruby" | "s" | "samp"
+        | "search" | "section" | "small" | "span" | "strong" | "sub" | "summary" | "sup"
+        | "table" | "tbody" | "tfoot" | "thead" | "title" | "tr" | "u" | "ul" | "var" | "wbr" => {
+            &[]
+        }
+        _ => return None,
+    };
+
+    Some(attributes)
+}
+
+/// Whether `name` can be written as an attribute name, which excludes spaces,
+/// quotes, `>`, `/`, `=`, control characters and noncharacters, matching the
+/// runtime check of spread attribute names
+pub fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|char| {
+            let is_noncharacter =
+                matches!(char, '\u{FDD0}'..='\u{FDEF}') || (char as u32 & 0xFFFE) == 0xFFFE;
+            !(char.is_control()
+                || is_noncharacter
+                || matches!(char, ' ' | '"' | '\'' | '>' | '/' | '='))
+        })
+}
+
+/// Whether an allow-list entry (`hx-*` or `x-data`) matches the attribute name
+fn allow_list_entry_matches(entry: &str, attribute_name: &str) -> bool {
+    match entry.strip_suffix('*') {
+        Some(prefix) => attribute_name.starts_with(prefix),
+        None => entry == attribute_name,
+    }
+}
+
+fn is_allow_listed(attribute_name: &str) -> bool {
+    DEFAULT_ALLOW_LIST
+        .iter()
+        .copied()
+        .chain(
+            CONFIGURED_ALLOW_LIST
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim),
+        )
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| allow_list_entry_matches(entry, attribute_name))
+}
+
+/// Checks that `attribute_name` can be used on a `<tag_name>` element.
+///
+/// Returns an error message, with a suggestion when a close match exists, for
+/// attributes unknown to the element.
+pub fn validate_attribute(tag_name: &str, attribute_name: &str) -> Result<(), String> {
+    let element_attributes = match element_attributes(tag_name) {
+        Some(attributes) => attributes,
+        None => return Ok(()),
+    };
+
+    let name = attribute_name.to_ascii_lowercase();
+    let known_attributes = || {
+        GLOBAL_ATTRIBUTES
+            .iter()
+            .chain(GLOBAL_EVENT_HANDLERS)
+            .chain(element_attributes)
+    };
+
+    if known_attributes().any(|known| *known == name)
+        || ALLOWED_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        || is_allow_listed(&name)
+    {
+        return Ok(());
+    }
+
+    match closest_match(&name, known_attributes().copied()) {
+        Some(known) => Err(format!(
+            "Unknown attribute `{}` on <{}>. Did you mean `{}`?",
+            attribute_name, tag_name, known
+        )),
+        None => Err(format!(
+            "Unknown attribute `{}` on <{}>. Use a `data-` prefix for custom data, or add it to the `{}` environment variable",
+            attribute_name, tag_name, ALLOW_LIST_ENV
+        )),
+    }
+}
+
+/// The candidate closest to `name`, if it is close enough to be a likely typo
+pub fn closest_match<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (edit_distance(candidate, name), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content_model::nesting_error;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn accepts_global_and_element_attributes() {
+        assert_eq!(validate_attribute("div", "class"), Ok(()));
+        assert_eq!(validate_attribute("a", "href"), Ok(()));
+        assert_eq!(validate_attribute("td", "colspan"), Ok(()));
+        assert_eq!(validate_attribute("button", "onclick"), Ok(()));
+        assert_eq!(validate_attribute("meta", "property"), Ok(()));
+    }
+
+    #[test]
+    fn accepts_prefixed_and_allow_listed_attributes() {
+        assert_eq!(validate_attribute("div", "data-id"), Ok(()));
+        assert_eq!(validate_attribute("div", "aria-label"), Ok(()));
+        assert_eq!(validate_attribute("div", "hx-get"), Ok(()));
+        assert_eq!(validate_attribute("div", "x-data"), Ok(()));
+        assert_eq!(validate_attribute("div", "xml:lang"), Ok(()));
+        assert_eq!(validate_attribute("div", "x-on:click.prevent"), Ok(()));
+        assert_eq!(validate_attribute("button", "@click"), Ok(()));
+        assert_eq!(validate_attribute("div", ":class"), Ok(()));
+    }
+
+    #[test]
+    fn skips_unknown_elements() {
+        assert_eq!(validate_attribute("svg", "viewBox"), Ok(()));
+        assert_eq!(validate_attribute("circle", "cx"), Ok(()));
+    }
+
+    #[test]
+    fn suggests_close_matches() {
+        assert_eq!(
+            validate_attribute("div", "clas"),
+            Err("Unknown attribute `clas` on <div>. Did you mean `class`?".to_string())
+        );
+        assert_eq!(
+            validate_attribute("a", "hreff"),
+            Err("Unknown attribute `hreff` on <a>. Did you mean `href`?".to_string())
+        );
+    }
+
+    fn nesting_message(ancestors: &[&str], tag_name: &str) -> Option<(String, bool)> {
+        nesting_error(ancestors, tag_name).map(|error| (error.message, error.changes_tree))
+    }
+
+    #[test]
+    fn validates_nesting() {
+        assert_eq!(nesting_message(&["ul"], "li"), None);
+        assert_eq!(nesting_message(&[], "li"), None);
+        assert_eq!(nesting_message(&["div", "p"], "span"), None);
+        assert_eq!(
+            nesting_message(&["div"], "li"),
+            Some((
+                "<li> can't be a child of <div>. Expected it inside <ul>, <ol>, <menu>".to_string(),
+                false
+            ))
+        );
+        assert_eq!(
+            nesting_message(&["div"], "td"),
+            Some((
+                "<td> can't be a child of <div>. Expected it inside <tr>".to_string(),
+                true
+            ))
+        );
+        assert_eq!(
+            nesting_message(&["p"], "div"),
+            Some((
+                "<div> can't be a child of <p>. Browsers close the <p> before it".to_string(),
+                true
+            ))
+        );
+        assert_eq!(
+            nesting_message(&["a", "span"], "a"),
+            Some(("<a> can't be nested inside <a>".to_string(), true))
+        );
+        assert_eq!(
+            nesting_message(&["button"], "a"),
+            Some(("<a> can't be nested inside <button>".to_string(), false))
+        );
+    }
+
+    #[test]
+    fn follows_the_current_standard() {
+        assert!(is_void_element("wbr"));
+        for obsolete in ["command", "keygen", "menuitem", "param"] {
+            assert!(!is_void_element(obsolete));
+        }
+
+        assert!(!is_void_element("search"));
+        assert_eq!(validate_attribute("search", "aria-label"), Ok(()));
+        assert!(validate_attribute("search", "action").is_err());
+        assert!(nesting_error(&["p"], "search").is_some());
+
+        assert_eq!(validate_attribute("input", "capture"), Ok(()));
+        assert_eq!(
+            validate_attribute("template", "shadowrootcustomelementregistry"),
+            Ok(())
+        );
+        assert_eq!(validate_attribute("button", "oncommand"), Ok(()));
+        for shadow_parts in ["part", "exportparts"] {
+            assert_eq!(validate_attribute("div", shadow_parts), Ok(()));
+        }
+    }
+
+    #[test]
+    fn rejects_element_attributes_on_other_elements() {
+        assert!(validate_attribute("div", "colspan").is_err());
+        assert!(validate_attribute("span", "href").is_err());
+    }
+}
@@ -1,52 +1,128 @@
 extern crate proc_macro;
 
+mod accessibility;
 mod child;
 mod children;
+mod comment;
+mod content_model;
 mod element;
 mod element_attribute;
 mod element_attributes;
+mod entity;
 mod function_component;
+mod html_entities;
+mod html_escaping;
+mod html_spec;
+mod root;
 mod tags;
+mod text;
 
-use element::Element;
 use proc_macro::TokenStream;
-use proc_macro_error::proc_macro_error;
+use proc_macro_error::{proc_macro_error, set_dummy};
 use quote::quote;
+use root::Root;
+use std::panic::AssertUnwindSafe;
 use syn::parse_macro_input;
 
+/// Runs a macro expanding to an expression. `proc_macro_error` writes emitted
+/// errors before the expansion, which isn't a valid expression, so both are
+/// wrapped in a block, like `{ compile_error! {..} expansion }`, keeping the type
+/// of the expansion and avoiding follow-up errors at the call site.
+fn expression_macro(expand: impl FnOnce() -> TokenStream) -> TokenStream {
+    let mut expansion = None;
+    let output = proc_macro_error::entry_point(
+        AssertUnwindSafe(|| {
+            let result = expand();
+            expansion = Some(result.to_string());
+            result
+        }),
+        false,
+    );
+
+    match expansion {
+        Some(expansion) if expansion == output.to_string() => output,
+        _ => {
+            let output = proc_macro2::TokenStream::from(output);
+            TokenStream::from(quote! {{ #output }})
+        }
+    }
+}
+
 #[proc_macro]
-#[proc_macro_error]
 pub fn html(input: TokenStream) -> TokenStream {
-    let el = proc_macro2::TokenStream::from(rsx(input));
-    let result = quote! { ::tide_jsx::Render::render(#el) };
-    TokenStream::from(result)
+    expression_macro(|| {
+        let el = parse_macro_input!(input as Root);
+        let result = quote! { ::tide_jsx::Render::render(&#el) };
+        set_dummy(result.clone());
+        TokenStream::from(result)
+    })
 }
 
 /// Generate a renderable component tree, before rendering it
 #[proc_macro]
-#[proc_macro_error]
 pub fn rsx(input: TokenStream) -> TokenStream {
-    let el = parse_macro_input!(input as Element);
-    let result = quote! { #el };
-    TokenStream::from(result)
+    expression_macro(|| {
+        let el = parse_macro_input!(input as Root);
+        let result = quote! { #el };
+        set_dummy(result.clone());
+        TokenStream::from(result)
+    })
 }
 
 #[proc_macro]
-#[proc_macro_error]
 pub fn view(input: TokenStream) -> TokenStream {
-    let el = parse_macro_input!(input as Element);
-    let result = quote! {
-      Ok(::tide::Response::builder(tide::http::StatusCode::Ok)
-            .content_type(::tide::http::mime::HTML)
-            .body(::tide_jsx::Render::render(#el))
-            .build())
-    };
-    TokenStream::from(result)
+    expression_macro(|| {
+        let el = parse_macro_input!(input as Root);
+        // Rendering failures become server errors instead of panics
+        let result = quote! {
+          match ::tide_jsx::Render::try_render(&#el) {
+              Ok(body) => Ok(::tide::Response::builder(::tide::http::StatusCode::Ok)
+                  .content_type(::tide::http::mime::HTML)
+                  .body(body)
+                  .build()),
+              Err(error) => Err(::tide::Error::new(
+                  ::tide::http::StatusCode::InternalServerError,
+                  error,
+              )),
+          }
+        };
+        set_dummy(result.clone());
+        TokenStream::from(result)
+    })
+}
+
+/// The list of void HTML elements, shared with the runtime crate
+#[doc(hidden)]
+#[proc_macro]
+pub fn void_elements(_input: TokenStream) -> TokenStream {
+    let names = html_spec::VOID_ELEMENTS;
+    TokenStream::from(quote! { &[#(#names),*] })
+}
+
+/// The list of boolean HTML attributes, shared with the runtime crate
+#[doc(hidden)]
+#[proc_macro]
+pub fn boolean_attributes(_input: TokenStream) -> TokenStream {
+    let names = html_spec::BOOLEAN_ATTRIBUTES;
+    TokenStream::from(quote! { &[#(#names),*] })
+}
+
+/// The content model rules and `nesting_error`, shared with the runtime crate's
+/// debug-mode validator
+#[doc(hidden)]
+#[proc_macro]
+pub fn content_model(_input: TokenStream) -> TokenStream {
+    include_str!("content_model.rs").parse().unwrap()
 }
 
+/// Turn a function into a component struct rendering its body.
+///
+/// Use `#[component(dyn)]` on layout components to render `Render`-bounded
+/// children through `tide_jsx::Children`, so the body is compiled only once.
 #[proc_macro_attribute]
 #[proc_macro_error]
-pub fn component(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn component(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let kind = parse_macro_input!(attr as function_component::ComponentKind);
     let f = parse_macro_input!(item as syn::ItemFn);
-    function_component::create_function_component(f)
+    function_component::create_function_component(f, kind)
 }
@@ -0,0 +1,56 @@
+use crate::accessibility::{self, LintLevels};
+use crate::child::Child;
+use crate::children::Children;
+use crate::comment::ConditionalComment;
+use crate::element::Element;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Result};
+
+/// The input of `rsx!`, `html!` and `view!`: lint attributes followed by an element,
+/// or by sibling nodes implicitly wrapped in a fragment
+pub struct Root {
+    element: Element,
+    warnings: proc_macro2::TokenStream,
+}
+
+impl Parse for Root {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lint_levels = LintLevels::parse_inner(input)?;
+        let mut children = input.parse::<Children>()?;
+        if ConditionalComment::peek_end(input) {
+            return Err(
+                input.error("Unexpected `<![endif]-->`, with no conditional comment to end")
+            );
+        }
+        if !input.is_empty() {
+            return Err(input.error("Unexpected closing tag, with no element to close"));
+        }
+
+        let element = match children.nodes.pop() {
+            Some(Child::Element(element)) if children.nodes.is_empty() => element,
+            last => {
+                children.nodes.extend(last);
+                Element::fragment(children)
+            }
+        };
+
+        let mut diagnostics = Vec::new();
+        element.check_content_model(&mut Vec::new(), &mut diagnostics);
+        let warnings = accessibility::check(&element, &lint_levels, diagnostics);
+
+        Ok(Self { element, warnings })
+    }
+}
+
+impl ToTokens for Root {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let element = &self.element;
+
+        if self.warnings.is_empty() {
+            element.to_tokens(tokens);
+        } else {
+            let warnings = &self.warnings;
+            quote!({ #warnings #element }).to_tokens(tokens);
+        }
+    }
+}
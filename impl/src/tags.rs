@@ -1,60 +1,272 @@
+use crate::element_attribute::{AttributeKey, ElementAttribute};
 use crate::element_attributes::ElementAttributes;
-use proc_macro_error::abort;
-use quote::quote;
+use crate::html_spec;
+use proc_macro2::Span;
+use proc_macro_error::emit_error;
+use quote::{quote, ToTokens};
+use std::cell::RefCell;
+use syn::ext::IdentExt;
+use syn::parse::discouraged::Speculative;
 use syn::parse::{Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
+thread_local! {
+    /// The names of the tags whose children are being parsed, outermost first
+    static OPEN_TAGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The name of a tag: a path for HTML elements and components, a dash-separated
+/// name for custom elements like `<my-widget>`, which are rendered verbatim, or
+/// a block evaluating to a tag name or component factory at runtime, like `<{tag}>`
+pub enum TagName {
+    Path(syn::Path),
+    Dashed(Punctuated<syn::Ident, syn::Token![-]>),
+    Dynamic(syn::Block),
+}
+
+impl TagName {
+    pub fn fragment() -> Self {
+        Self::Path(syn::parse_str::<syn::Path>("tide_jsx::Fragment").unwrap())
+    }
+
+    pub fn as_path(&self) -> Option<&syn::Path> {
+        match self {
+            Self::Path(path) => Some(path),
+            Self::Dashed(_) | Self::Dynamic(_) => None,
+        }
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, Self::Dynamic(_))
+    }
+
+    /// The name as written in the markup, with dashes but no spaces
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::Path(path) => quote!(#path).to_string(),
+            Self::Dashed(words) => words
+                .iter()
+                .map(|word| word.unraw().to_string())
+                .collect::<Vec<_>>()
+                .join("-"),
+            Self::Dynamic(block) => quote!(#block).to_string(),
+        }
+    }
+
+    pub fn is_fragment(&self) -> bool {
+        self.as_string() == quote!(tide_jsx::Fragment).to_string()
+    }
+
+    /// Whether the tag is a component, written as a path or with a leading capital
+    pub fn is_component(&self) -> bool {
+        match self {
+            Self::Path(path) => match path.get_ident() {
+                None => true,
+                Some(ident) => {
+                    let name = ident.to_string();
+                    let first_letter = name.get(0..1).unwrap();
+                    first_letter.to_uppercase() == first_letter
+                }
+            },
+            Self::Dashed(_) | Self::Dynamic(_) => false,
+        }
+    }
+
+    /// Parses a tag name, or an empty one for fragments
+    fn parse_or_fragment(input: ParseStream) -> Result<Self> {
+        if input.peek(syn::token::Brace) {
+            Ok(Self::Dynamic(input.parse()?))
+        } else if input.peek(syn::Ident::peek_any) && input.peek2(syn::Token![-]) {
+            let words = Punctuated::parse_separated_nonempty_with(input, syn::Ident::parse_any)?;
+            let name = Self::Dashed(words);
+            if name.as_string().chars().any(char::is_uppercase) {
+                emit_error!(
+                    name,
+                    "Custom element names must be lowercase, like <{}>",
+                    name.as_string().to_lowercase()
+                );
+            }
+            Ok(name)
+        } else if input.peek(syn::Ident::peek_any) && !input.peek2(syn::Token![::]) {
+            // Keywords are valid tag names, like the `<use>` of SVG
+            Ok(Self::Path(syn::Ident::parse_any(input)?.into()))
+        } else if input.peek(syn::Ident::peek_any) || input.peek(syn::Token![::]) {
+            Ok(Self::Path(syn::Path::parse_mod_style(input)?))
+        } else {
+            Ok(Self::fragment())
+        }
+    }
+}
+
+impl ToTokens for TagName {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::Path(path) => path.to_tokens(tokens),
+            Self::Dashed(words) => words.to_tokens(tokens),
+            Self::Dynamic(block) => block.to_tokens(tokens),
+        }
+    }
+}
+
+/// The `{..}` block of an element: struct update syntax for components, and
+/// attributes to spread for plain elements
 pub struct FallbackAttributes(pub syn::Block);
+
+impl FallbackAttributes {
+    /// The collection of a `{..attributes}` block
+    pub fn spread_expr(&self) -> Option<&syn::Expr> {
+        match self.0.stmts.as_slice() {
+            [syn::Stmt::Expr(syn::Expr::Range(syn::ExprRange {
+                from: None,
+                limits: syn::RangeLimits::HalfOpen(_),
+                to: Some(expr),
+                ..
+            }))] => Some(expr),
+            _ => None,
+        }
+    }
+}
+
 pub struct OpenTag {
-    pub name: syn::Path,
+    pub name: TagName,
+    /// The span of the tag name, or of the `<` of fragments
+    pub span: Span,
     pub attributes: ElementAttributes,
     pub self_closing: bool,
     pub fallback_attributes: Option<FallbackAttributes>,
 }
 
-fn name_or_fragment(maybe_name: Result<syn::Path>) -> syn::Path {
-    maybe_name.unwrap_or_else(|_| syn::parse_str::<syn::Path>("tide_jsx::Fragment").unwrap())
+/// The name of a tag as written in the markup, empty for fragments
+fn tag_display_name(name: &TagName) -> String {
+    if name.is_fragment() {
+        String::new()
+    } else {
+        name.as_string()
+    }
+}
+
+/// Keeps a tag on the stack of open tags while its children are parsed
+pub struct OpenTagScope(());
+
+impl Drop for OpenTagScope {
+    fn drop(&mut self) {
+        OPEN_TAGS.with(|open_tags| open_tags.borrow_mut().pop());
+    }
 }
 
-fn is_custom_element_name(path: &syn::Path) -> bool {
-    match path.get_ident() {
-        None => true,
-        Some(ident) => {
-            let name = ident.to_string();
-            let first_letter = name.get(0..1).unwrap();
-            first_letter.to_uppercase() == first_letter
+impl OpenTag {
+    pub fn display_name(&self) -> String {
+        tag_display_name(&self.name)
+    }
+
+    /// Marks this tag as open until the returned scope is dropped
+    pub fn enter(&self) -> OpenTagScope {
+        OPEN_TAGS.with(|open_tags| open_tags.borrow_mut().push(self.display_name()));
+        OpenTagScope(())
+    }
+
+    /// Parses the closing tag of this element, recovering from mismatches:
+    /// a closing tag of an outer element means this one was never closed and is left
+    /// for the outer element, while any other closing tag is reported and consumed.
+    /// Returns the closing tag, or `None` if the element was never closed.
+    pub fn parse_closing_tag(&self, input: ParseStream) -> Result<Option<ClosingTag>> {
+        if input.is_empty() {
+            self.emit_never_closed();
+            return Ok(None);
+        }
+
+        let fork = input.fork();
+        let closing_tag = fork.parse::<ClosingTag>()?;
+        let closing_name = tag_display_name(&closing_tag.name);
+
+        if closing_name != self.display_name() {
+            if OPEN_TAGS.with(|open_tags| open_tags.borrow().contains(&closing_name)) {
+                self.emit_never_closed();
+                return Ok(None);
+            }
+
+            emit_error!(
+                closing_tag.span,
+                "Expected closing tag for: <{}>, found </{}>",
+                self.display_name(),
+                closing_name
+            );
+        }
+
+        input.advance_to(&fork);
+        Ok(Some(closing_tag))
+    }
+
+    fn emit_never_closed(&self) {
+        emit_error!(
+            self.span,
+            "this `<{}>` was never closed",
+            self.display_name()
+        );
+    }
+
+    /// Whether whitespace is significant in the element, like in `<pre>`
+    pub fn preserves_whitespace(&self) -> bool {
+        match self.name.as_path().and_then(syn::Path::get_ident) {
+            Some(ident) => html_spec::preserves_whitespace(&ident.to_string()),
+            None => false,
+        }
+    }
+
+    pub fn is_void_element(&self) -> bool {
+        match self.name.as_path().and_then(syn::Path::get_ident) {
+            Some(ident) => html_spec::is_void_element(&ident.to_string()),
+            None => false,
         }
     }
 }
 
 impl Parse for OpenTag {
     fn parse(input: ParseStream) -> Result<Self> {
-        input.parse::<syn::Token![<]>()?;
-        let maybe_name = syn::Path::parse_mod_style(input);
-        let name = name_or_fragment(maybe_name);
-        let is_custom_element = is_custom_element_name(&name);
-        let attributes = ElementAttributes::parse(input, is_custom_element)?;
+        let lt = input.parse::<syn::Token![<]>()?;
+        let name = TagName::parse_or_fragment(input)?;
+        let span = if name.is_fragment() {
+            lt.span
+        } else {
+            name.span()
+        };
+        let is_custom_element = name.is_component();
+        let mut attributes = parse_shorthand(input)?;
+        if is_custom_element && !attributes.attributes.is_empty() {
+            emit_error!(
+                span,
+                "Only HTML elements can have `.class` and `#id` shorthand. Use props instead"
+            );
+            attributes = ElementAttributes::default();
+        }
+        attributes.extend(ElementAttributes::parse(input, &name, is_custom_element)?);
 
         let fallback = if input.peek(syn::token::Brace) {
-            let block = input.parse::<syn::Block>()?;
-            if !is_custom_element {
-                abort!(
-                    block.span(),
-                    "Only custom components can have fallback attributes",
+            let fallback = FallbackAttributes(input.parse::<syn::Block>()?);
+            if is_custom_element {
+                Some(fallback)
+            } else if fallback.spread_expr().is_none() {
+                emit_error!(
+                    fallback.0,
+                    "Expected attributes to spread, like `{..attributes}`. Only custom components can have other fallback attributes",
                 );
+                None
+            } else {
+                // Explicit attributes can follow spread ones, which they override anyway
+                attributes.extend(ElementAttributes::parse(input, &name, false)?);
+                Some(fallback)
             }
-            Some(FallbackAttributes(block))
         } else {
             None
         };
 
-        if !is_custom_element && fallback.is_some() {}
-
         let self_closing = input.parse::<syn::Token![/]>().is_ok();
         input.parse::<syn::Token![>]>()?;
 
         Ok(Self {
             name,
+            span,
             attributes,
             self_closing,
             fallback_attributes: fallback,
@@ -62,34 +274,88 @@ impl Parse for OpenTag {
     }
 }
 
-pub struct ClosingTag {
-    name: syn::Path,
-}
+/// Parses the `.class` and `#id` shorthand following a tag name, like
+/// `<div.card.shadow#main>`. Classes become `class:name=true` directives, merged
+/// with the `class` attribute like other directives.
+fn parse_shorthand(input: ParseStream) -> Result<ElementAttributes> {
+    let mut attributes = Vec::new();
+    let mut has_id = false;
 
-impl ClosingTag {
-    pub fn validate(&self, open_tag: &OpenTag) {
-        let open_tag_path = &open_tag.name;
-        let open_tag_path_str = quote!(#open_tag_path).to_string();
-        let self_path = &self.name;
-        let self_path_str = quote!(#self_path).to_string();
-        if self_path_str != open_tag_path_str {
-            abort!(
-                self.name.span(),
-                "Expected closing tag for: <{}>",
-                &open_tag_path_str
-            );
+    loop {
+        if input.peek(syn::Token![.]) {
+            let dot = input.parse::<syn::Token![.]>()?;
+            let (class, written) = parse_shorthand_name(input)?;
+            let key = AttributeKey::Verbatim(format!("class:{}", class), quote!(#dot #written));
+            let value = syn::Lit::Bool(syn::LitBool::new(true, dot.span));
+            let attribute = ElementAttribute::WithValueLit(key, value);
+            if !attributes.contains(&attribute) {
+                attributes.push(attribute);
+            }
+        } else if input.peek(syn::Token![#]) {
+            let hash = input.parse::<syn::Token![#]>()?;
+            let (id, written) = parse_shorthand_name(input)?;
+            if has_id {
+                emit_error!(written, "Elements can only have one `#id`");
+                continue;
+            }
+            has_id = true;
+
+            let mut key = Punctuated::new();
+            key.push(syn::Ident::new("id", hash.span));
+            let value = syn::Lit::Str(syn::LitStr::new(&id, written.span()));
+            attributes.push(ElementAttribute::WithValueLit(
+                AttributeKey::Dashed(key),
+                value,
+            ));
+        } else {
+            return Ok(ElementAttributes::new(attributes));
         }
     }
 }
 
+/// Parses a class or id name of the shorthand, made of identifiers and numbers
+/// separated by dashes, like `card` or `mt-4`
+fn parse_shorthand_name(input: ParseStream) -> Result<(String, proc_macro2::TokenStream)> {
+    let first = syn::Ident::parse_any(input)?;
+    let mut name = first.unraw().to_string();
+    let mut written = quote!(#first);
+
+    while input.peek(syn::Token![-])
+        && (input.peek2(syn::Ident::peek_any) || input.peek2(syn::LitInt))
+    {
+        let dash = input.parse::<syn::Token![-]>()?;
+        let (part, part_written) = if input.peek(syn::LitInt) {
+            let number = input.parse::<syn::LitInt>()?;
+            (number.to_string(), number.into_token_stream())
+        } else {
+            let ident = syn::Ident::parse_any(input)?;
+            (ident.unraw().to_string(), ident.into_token_stream())
+        };
+        name.push('-');
+        name.push_str(&part);
+        written.extend(quote!(#dash #part_written));
+    }
+
+    Ok((name, written))
+}
+
+pub struct ClosingTag {
+    pub name: TagName,
+    /// The span of the tag name, or of the `<` of fragments
+    pub span: Span,
+}
+
 impl Parse for ClosingTag {
     fn parse(input: ParseStream) -> Result<Self> {
-        input.parse::<syn::Token![<]>()?;
+        let lt = input.parse::<syn::Token![<]>()?;
         input.parse::<syn::Token![/]>()?;
-        let maybe_name = input.parse::<syn::Path>();
+        let name = TagName::parse_or_fragment(input)?;
+        let span = if name.is_fragment() {
+            lt.span
+        } else {
+            name.span()
+        };
         input.parse::<syn::Token![>]>()?;
-        Ok(Self {
-            name: name_or_fragment(maybe_name),
-        })
+        Ok(Self { name, span })
     }
 }
@@ -0,0 +1,145 @@
+use crate::html_escaping::escape_html;
+use proc_macro2::LineColumn;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Result};
+
+/// Bare text, like `"Hello"` or `{"Hello"}`, a `<_ />` space, or the whitespace
+/// written between two sibling nodes
+pub struct Text {
+    value: String,
+    kind: TextKind,
+    /// Whether whitespace is kept as written, like in `<pre>`
+    preserve_whitespace: bool,
+}
+
+enum TextKind {
+    Literal,
+    Space,
+    /// Whitespace between sibling nodes, with `value` approximating it as written
+    Gap {
+        same_line: bool,
+        /// Whether the text next to it already has whitespace on this side
+        next_to_whitespace: bool,
+    },
+}
+
+impl Text {
+    pub fn new(value: String) -> Self {
+        Self {
+            value,
+            kind: TextKind::Literal,
+            preserve_whitespace: false,
+        }
+    }
+
+    pub fn peek_space(input: ParseStream) -> bool {
+        input.peek(syn::Token![<]) && input.peek2(syn::Token![_])
+    }
+
+    /// Parses a `<_ />` space, for whitespace between sibling nodes
+    pub fn parse_space(input: ParseStream) -> Result<Self> {
+        input.parse::<syn::Token![<]>()?;
+        input.parse::<syn::Token![_]>()?;
+        input.parse::<syn::Token![/]>()?;
+        input.parse::<syn::Token![>]>()?;
+        Ok(Self {
+            value: " ".to_string(),
+            kind: TextKind::Space,
+            preserve_whitespace: false,
+        })
+    }
+
+    /// The whitespace between a node ending at `end` and the next one starting at
+    /// `start`, if there is any. Line breaks are written as is and indentation as
+    /// spaces, since only the positions of the nodes are known.
+    pub fn gap(
+        end: LineColumn,
+        start: LineColumn,
+        previous: Option<&Text>,
+        next: Option<&Text>,
+    ) -> Option<Self> {
+        let value = if start.line == end.line && start.column > end.column {
+            " ".repeat(start.column - end.column)
+        } else if start.line > end.line {
+            "\n".repeat(start.line - end.line) + &" ".repeat(start.column)
+        } else {
+            return None;
+        };
+
+        let next_to_whitespace = previous
+            .is_some_and(|text| text.text().ends_with(char::is_whitespace))
+            || next.is_some_and(|text| text.text().starts_with(char::is_whitespace));
+        Some(Self {
+            value,
+            kind: TextKind::Gap {
+                same_line: start.line == end.line,
+                next_to_whitespace,
+            },
+            preserve_whitespace: false,
+        })
+    }
+
+    pub fn preserve_whitespace(&mut self) {
+        self.preserve_whitespace = true;
+    }
+
+    /// The text, with whitespace handled like in JSX, unless it is preserved:
+    ///
+    /// - Literals spanning several lines have their lines trimmed, blank lines
+    ///   dropped and the others joined with a space, while literals on a single
+    ///   line are kept as written.
+    /// - Whitespace between nodes on the same line is a single space, unless the
+    ///   text next to it already has some, and whitespace with line breaks is dropped.
+    pub fn text(&self) -> String {
+        match self.kind {
+            _ if self.preserve_whitespace => self.value.clone(),
+            TextKind::Space => self.value.clone(),
+            TextKind::Gap {
+                same_line,
+                next_to_whitespace,
+            } => {
+                if same_line && !next_to_whitespace {
+                    " ".to_string()
+                } else {
+                    String::new()
+                }
+            }
+            TextKind::Literal if !self.value.contains('\n') => self.value.clone(),
+            TextKind::Literal => {
+                let lines: Vec<_> = self.value.lines().collect();
+                let last = lines.len() - 1;
+                lines
+                    .iter()
+                    .enumerate()
+                    .map(|(index, line)| {
+                        let line = if index == 0 { line } else { line.trim_start() };
+                        if index == last {
+                            line
+                        } else {
+                            line.trim_end()
+                        }
+                    })
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+        }
+    }
+
+    pub fn static_html(&self) -> Option<String> {
+        Some(escape_html(&self.text()))
+    }
+}
+
+impl Parse for Text {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self::new(input.parse::<syn::LitStr>()?.value()))
+    }
+}
+
+impl ToTokens for Text {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let html = escape_html(&self.text());
+        quote! { tide_jsx::Raw::from(#html) }.to_tokens(tokens);
+    }
+}
@@ -0,0 +1,151 @@
+use std::borrow::Cow;
+
+/// Attributes whose presence means `true`, like `disabled`
+const BOOLEAN_ATTRIBUTES: &[&str] = tide_jsx_impl::boolean_attributes!();
+
+/// A value that can be written in an HTML attribute, which decides whether
+/// the attribute is emitted at all:
+///
+/// - strings, numbers and booleans are written as they are, like `aria-pressed="false"`
+/// - on boolean attributes, `true` writes an empty value, like `disabled=""`, and
+///   `false` leaves the attribute out
+/// - `None` leaves the attribute out
+pub trait AttributeValue<'a> {
+    /// The attribute value, or `None` to leave the attribute out
+    fn into_attribute_value(self) -> Option<Cow<'a, str>>;
+
+    /// The value of a boolean attribute like `disabled`, or `None` to leave it out
+    fn into_boolean_attribute_value(self) -> Option<Cow<'a, str>>
+    where
+        Self: Sized,
+    {
+        self.into_attribute_value()
+    }
+}
+
+/// The value of an attribute spread onto an element, whose name is only known at
+/// runtime, following `into_boolean_attribute_value` for boolean attributes
+#[doc(hidden)]
+pub fn spread_attribute_value<'a>(
+    name: &str,
+    value: impl AttributeValue<'a>,
+) -> Option<Cow<'a, str>> {
+    let is_boolean = BOOLEAN_ATTRIBUTES
+        .iter()
+        .any(|boolean| boolean.eq_ignore_ascii_case(name));
+    if is_boolean {
+        value.into_boolean_attribute_value()
+    } else {
+        value.into_attribute_value()
+    }
+}
+
+impl<'a> AttributeValue<'a> for &'a str {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl<'a> AttributeValue<'a> for String {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(Cow::Owned(self))
+    }
+}
+
+impl<'a> AttributeValue<'a> for &'a String {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl<'a> AttributeValue<'a> for Cow<'a, str> {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(self)
+    }
+}
+
+impl<'a> AttributeValue<'a> for &'a Cow<'_, str> {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl<'a> AttributeValue<'a> for bool {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(Cow::Borrowed(if self { "true" } else { "false" }))
+    }
+
+    fn into_boolean_attribute_value(self) -> Option<Cow<'a, str>> {
+        self.then_some(Cow::Borrowed(""))
+    }
+}
+
+impl<'a> AttributeValue<'a> for &bool {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        (*self).into_attribute_value()
+    }
+
+    fn into_boolean_attribute_value(self) -> Option<Cow<'a, str>> {
+        (*self).into_boolean_attribute_value()
+    }
+}
+
+impl<'a, T: AttributeValue<'a>> AttributeValue<'a> for Option<T> {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        self.and_then(AttributeValue::into_attribute_value)
+    }
+
+    fn into_boolean_attribute_value(self) -> Option<Cow<'a, str>> {
+        self.and_then(AttributeValue::into_boolean_attribute_value)
+    }
+}
+
+macro_rules! display_attribute_value_impl {
+    ($t:ty) => {
+        impl<'a> AttributeValue<'a> for $t {
+            fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+                Some(Cow::Owned(self.to_string()))
+            }
+        }
+
+        impl<'a> AttributeValue<'a> for &$t {
+            fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+                Some(Cow::Owned(self.to_string()))
+            }
+        }
+    };
+}
+
+display_attribute_value_impl!(char);
+display_attribute_value_impl!(f32);
+display_attribute_value_impl!(f64);
+display_attribute_value_impl!(i128);
+display_attribute_value_impl!(i16);
+display_attribute_value_impl!(i32);
+display_attribute_value_impl!(i64);
+display_attribute_value_impl!(i8);
+display_attribute_value_impl!(isize);
+display_attribute_value_impl!(u128);
+display_attribute_value_impl!(u16);
+display_attribute_value_impl!(u32);
+display_attribute_value_impl!(u64);
+display_attribute_value_impl!(u8);
+display_attribute_value_impl!(usize);
+display_attribute_value_impl!(std::net::IpAddr);
+
+#[cfg(feature = "uuid")]
+display_attribute_value_impl!(uuid::Uuid);
+
+#[cfg(feature = "url")]
+impl<'a> AttributeValue<'a> for url::Url {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(Cow::Owned(self.into()))
+    }
+}
+
+#[cfg(feature = "url")]
+impl<'a> AttributeValue<'a> for &'a url::Url {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        Some(Cow::Borrowed(self.as_str()))
+    }
+}
@@ -9,6 +9,14 @@ use crate::Render;
 /// # Example
 ///
 /// ```
+/// use tide_jsx::{branch, component, rsx, Render};
+///
+/// #[component]
+/// fn NotificationWidget() {
+///     rsx! { <ul class="notifications" /> }
+/// }
+///
+/// let notifications: Vec<&str> = Vec::new();
 /// let fragment = branch(
 ///     notifications.is_empty(),
 ///     || {
@@ -23,6 +31,7 @@ use crate::Render;
 ///         }
 ///     },
 /// );
+/// assert_eq!(fragment.render(), "<p>No notifications at this time</p>");
 /// ```
 ///
 /// In this example, if `notifications.is_empty()` is true, it will render a paragraph
@@ -0,0 +1,28 @@
+use crate::Render;
+use std::fmt::{Debug, Formatter, Result};
+
+/// Type-erased children of a component.
+///
+/// `#[component(dyn)]` hands its `Render`-bounded parameters to the render body as
+/// `Children`, so the body is compiled once instead of for every call site.
+/// It borrows the children, so erasing them doesn't allocate.
+#[derive(Clone, Copy)]
+pub struct Children<'a>(&'a dyn Render);
+
+impl<'a> Children<'a> {
+    pub fn new<T: Render>(children: &'a T) -> Self {
+        Children(children)
+    }
+}
+
+impl Render for Children<'_> {
+    fn render_into(&self, writer: &mut String) -> Result {
+        self.0.render_into(writer)
+    }
+}
+
+impl Debug for Children<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str("Children")
+    }
+}
@@ -0,0 +1,89 @@
+use crate::AttributeValue;
+use std::borrow::Cow;
+
+/// A `class` attribute value, built from class names in order, without
+/// duplicates.
+///
+/// This is what `rsx!` generates to merge `class` with `class:name={..}` directives.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassList {
+    classes: Vec<String>,
+}
+
+impl ClassList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds whitespace-separated class names
+    pub fn push(&mut self, classes: &str) {
+        for class in classes.split_whitespace() {
+            if !self.classes.iter().any(|existing| existing == class) {
+                self.classes.push(class.to_string());
+            }
+        }
+    }
+
+    /// Adds whitespace-separated class names from an attribute value, skipping
+    /// the ones leaving the attribute out, like `None`
+    pub fn push_value<'a>(&mut self, classes: impl AttributeValue<'a>) {
+        if let Some(classes) = classes.into_attribute_value() {
+            self.push(&classes);
+        }
+    }
+
+    /// Adds whitespace-separated class names if `condition` holds
+    pub fn push_if(&mut self, classes: &str, condition: bool) {
+        if condition {
+            self.push(classes);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+}
+
+impl<'a> AttributeValue<'a> for ClassList {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        (&self).into_attribute_value()
+    }
+}
+
+impl<'a> AttributeValue<'a> for &ClassList {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(Cow::Owned(self.classes.join(" ")))
+        }
+    }
+}
+
+/// Builds a [`ClassList`], from class names and `class => condition` pairs.
+/// Values like `Option`s are skipped when they would leave an attribute out.
+///
+/// ```
+/// use tide_jsx::{classes, html};
+///
+/// let primary = true;
+/// let extra_class: Option<&str> = None;
+/// let class = classes!["btn", "btn-primary" => primary, "btn-link" => !primary, extra_class];
+/// assert_eq!(html! { <button class={class} /> }, r#"<button class="btn btn-primary"></button>"#);
+/// ```
+#[macro_export]
+macro_rules! classes {
+    (@push $list:ident, $classes:expr, $condition:expr) => {
+        if $condition {
+            $list.push_value($classes)
+        }
+    };
+    (@push $list:ident, $classes:expr) => {
+        $list.push_value($classes)
+    };
+    ($($classes:expr $(=> $condition:expr)?),* $(,)?) => {{
+        let mut list = $crate::ClassList::new();
+        $($crate::classes!(@push list, $classes $(, $condition)?);)*
+        list
+    }};
+}
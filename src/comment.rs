@@ -0,0 +1,78 @@
+use crate::html_escaping::escape_comment;
+use crate::render;
+use crate::Render;
+use std::borrow::Cow;
+use std::fmt::Result;
+
+/// An HTML comment, written `<!-- "text" -->` in `rsx!`.
+///
+/// Dashes in the text are spaced out so it can't close the comment early.
+/// With the `strip-comments` feature, comments aren't rendered at all.
+#[derive(Debug, Clone)]
+pub struct Comment<'a>(Cow<'a, str>);
+
+impl<'a> Comment<'a> {
+    pub fn new(text: impl Into<Cow<'a, str>>) -> Self {
+        Comment(text.into())
+    }
+}
+
+impl Render for Comment<'_> {
+    fn render_into(&self, writer: &mut String) -> Result {
+        if cfg!(feature = "strip-comments") {
+            return Ok(());
+        }
+
+        writer.push_str("<!-- ");
+        escape_comment(&self.0, writer)?;
+        writer.push_str(" -->");
+        Ok(())
+    }
+}
+
+/// A conditional comment, written `<!--[if mso]> ... <![endif]-->` in `rsx!`, whose
+/// contents are only read by the clients matching the condition, like Outlook.
+///
+/// Conditional comments are markup for these clients, so they are kept with the
+/// `strip-comments` feature. Rendering fails for conditions that aren't made of
+/// letters, digits, dots, spaces and the `!`, `&`, `|`, `(` and `)` operators.
+#[derive(Debug, Clone)]
+pub struct ConditionalComment<'a, T> {
+    condition: &'a str,
+    contents: T,
+}
+
+impl<'a, T> ConditionalComment<'a, T> {
+    pub fn new(condition: &'a str, contents: T) -> Self {
+        ConditionalComment {
+            condition,
+            contents,
+        }
+    }
+}
+
+impl<T: Render> Render for ConditionalComment<'_, T> {
+    fn render_into(&self, writer: &mut String) -> Result {
+        if !is_valid_condition(self.condition) {
+            return Err(render::fail(format!(
+                "invalid conditional comment condition {:?}",
+                self.condition
+            )));
+        }
+
+        writer.push_str("<!--[if ");
+        writer.push_str(self.condition);
+        writer.push_str("]>");
+        self.contents.render_into(writer)?;
+        writer.push_str("<![endif]-->");
+        Ok(())
+    }
+}
+
+/// Whether a condition can be written in a conditional comment as is
+fn is_valid_condition(condition: &str) -> bool {
+    !condition.trim().is_empty()
+        && condition
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " .!&|()".contains(c))
+}
@@ -0,0 +1,109 @@
+//! Validation of element nesting, like `<div>` inside `<p>` or `<li>` outside of a list.
+//!
+//! `rsx!` already rejects invalid nesting it can see. In debug builds, rendering also
+//! tracks the open elements, so nesting that crosses component boundaries is logged
+//! as a warning with the `log` crate.
+
+#[cfg(debug_assertions)]
+use std::cell::RefCell;
+
+tide_jsx_impl::content_model!();
+
+#[cfg(debug_assertions)]
+thread_local! {
+    static OPEN_ELEMENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Keeps an element on the open elements stack while its contents render
+#[cfg(debug_assertions)]
+pub(crate) struct OpenElement(());
+
+/// Pushes `tag_name` on the open elements stack, reporting invalid nesting
+#[cfg(debug_assertions)]
+pub(crate) fn open_element(tag_name: &str) -> OpenElement {
+    OPEN_ELEMENTS.with(|open_elements| {
+        let mut open_elements = open_elements.borrow_mut();
+        let ancestors: Vec<_> = open_elements.iter().map(String::as_str).collect();
+        if let Some(error) = nesting_error(&ancestors, tag_name) {
+            log::warn!("tide-jsx: invalid nesting: {}", error);
+        }
+        open_elements.push(tag_name.to_string());
+    });
+
+    OpenElement(())
+}
+
+#[cfg(debug_assertions)]
+impl Drop for OpenElement {
+    fn drop(&mut self) {
+        OPEN_ELEMENTS.with(|open_elements| {
+            open_elements.borrow_mut().pop();
+        });
+    }
+}
+
+/// Checks the nesting of elements rendered at compile time, given in document order
+/// with their depth, as if they were rendered one by one
+#[cfg(debug_assertions)]
+pub(crate) fn check_static_elements(elements: &[(usize, &str)]) {
+    let mut open_elements = Vec::new();
+    for (depth, tag_name) in elements {
+        open_elements.truncate(*depth);
+        open_elements.push(open_element(tag_name));
+    }
+}
+
+/// Starts a fresh open elements stack for a top-level render, restoring the
+/// previous one when dropped
+#[cfg(debug_assertions)]
+pub(crate) struct RenderScope(Vec<String>);
+
+#[cfg(debug_assertions)]
+impl RenderScope {
+    pub(crate) fn new() -> Self {
+        RenderScope(OPEN_ELEMENTS.with(|open_elements| open_elements.take()))
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for RenderScope {
+    fn drop(&mut self) {
+        OPEN_ELEMENTS.with(|open_elements| {
+            *open_elements.borrow_mut() = std::mem::take(&mut self.0);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn validates_nesting() {
+        assert_eq!(nesting_error(&["ol"], "li"), None);
+        assert_eq!(nesting_error(&["table", "tbody"], "tr"), None);
+        assert_eq!(
+            nesting_error(&["ul", "li", "p"], "ul"),
+            Some(NestingError {
+                message: "<ul> can't be a child of <p>. Browsers close the <p> before it"
+                    .to_string(),
+                changes_tree: true,
+            })
+        );
+        assert_eq!(
+            nesting_error(&["button", "span"], "button"),
+            Some(NestingError {
+                message: "<button> can't be nested inside <button>".to_string(),
+                changes_tree: true,
+            })
+        );
+        assert_eq!(
+            nesting_error(&["label"], "label"),
+            Some(NestingError {
+                message: "<label> can't be nested inside <label>".to_string(),
+                changes_tree: false,
+            })
+        );
+    }
+}
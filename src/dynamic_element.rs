@@ -0,0 +1,107 @@
+use crate::simple_element::{render_element, Attributes};
+use crate::Render;
+use std::fmt::Result;
+
+/// An element whose tag is chosen at runtime, written `<{tag}>` in `rsx!`
+#[derive(Debug, Clone)]
+pub struct DynamicElement<'a, Tag, T: Render> {
+    pub tag: Tag,
+    pub attributes: Attributes<'a>,
+    pub contents: Option<T>,
+}
+
+/// What can be placed in `<{tag}>`: a tag name, or a component factory rendering
+/// the element's attributes and children, given as [`DynamicProps`], into the
+/// writer, like `fn(DynamicProps, &mut String) -> fmt::Result`
+pub trait DynamicTag {
+    fn render_element(
+        &self,
+        attributes: &Attributes<'_>,
+        contents: Option<&dyn Render>,
+        writer: &mut String,
+    ) -> Result;
+}
+
+impl DynamicTag for &str {
+    fn render_element(
+        &self,
+        attributes: &Attributes<'_>,
+        contents: Option<&dyn Render>,
+        writer: &mut String,
+    ) -> Result {
+        render_element(self, attributes, contents, writer)
+    }
+}
+
+impl DynamicTag for String {
+    fn render_element(
+        &self,
+        attributes: &Attributes<'_>,
+        contents: Option<&dyn Render>,
+        writer: &mut String,
+    ) -> Result {
+        render_element(self, attributes, contents, writer)
+    }
+}
+
+impl<F> DynamicTag for F
+where
+    F: Fn(DynamicProps<'_, '_>, &mut String) -> Result,
+{
+    fn render_element(
+        &self,
+        attributes: &Attributes<'_>,
+        contents: Option<&dyn Render>,
+        writer: &mut String,
+    ) -> Result {
+        self(
+            DynamicProps {
+                attributes,
+                children: contents,
+            },
+            writer,
+        )
+    }
+}
+
+impl<Tag, T> Render for DynamicElement<'_, Tag, T>
+where
+    Tag: DynamicTag + Send + Sync,
+    T: Render,
+{
+    fn render_into(&self, writer: &mut String) -> Result {
+        let contents = self
+            .contents
+            .as_ref()
+            .map(|contents| contents as &dyn Render);
+        self.tag.render_element(&self.attributes, contents, writer)
+    }
+}
+
+/// The attributes and children given to a component chosen at runtime
+#[derive(Clone, Copy)]
+pub struct DynamicProps<'p, 'a> {
+    attributes: &'p Attributes<'a>,
+    children: Option<&'p dyn Render>,
+}
+
+impl<'p> DynamicProps<'p, '_> {
+    pub fn attribute(&self, name: &str) -> Option<&'p str> {
+        self.attributes
+            .as_ref()
+            .and_then(|attributes| attributes.get(name))
+            .map(|value| value.as_ref())
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = (&'p str, &'p str)> {
+        self.attributes
+            .iter()
+            .flatten()
+            .map(|(name, value)| (name.as_ref(), value.as_ref()))
+    }
+
+    /// Renders the children of the element, if any
+    pub fn children(&self) -> Option<&'p dyn Render> {
+        self.children
+    }
+}
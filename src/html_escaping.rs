@@ -14,3 +14,18 @@ pub fn escape_html<W: Write>(html: &str, writer: &mut W) -> Result {
 
     Ok(())
 }
+
+/// Escapes the text of an HTML comment, putting spaces between consecutive
+/// dashes so the text can't close the comment early
+pub fn escape_comment<W: Write>(text: &str, writer: &mut W) -> Result {
+    let mut previous = None;
+    for c in text.chars() {
+        if c == '-' && previous == Some('-') {
+            writer.write_char(' ')?;
+        }
+        writer.write_char(c)?;
+        previous = Some(c);
+    }
+
+    Ok(())
+}
@@ -1,15 +1,30 @@
+mod attribute_value;
 mod branch;
+mod children;
+mod class_list;
+mod comment;
+pub mod content_model;
+mod dynamic_element;
 pub mod fragment;
 pub mod html;
 pub mod html_escaping;
 mod numbers;
 mod render;
+mod render_fn;
 mod simple_element;
+mod style_list;
 mod text_element;
 
-pub use self::render::{BoxedRender, Render};
+pub use self::render::{BoxedRender, Render, RenderError};
+pub use attribute_value::{spread_attribute_value, AttributeValue};
 pub use branch::branch;
+pub use children::Children;
+pub use class_list::ClassList;
+pub use comment::{Comment, ConditionalComment};
+pub use dynamic_element::{DynamicElement, DynamicProps, DynamicTag};
 pub use fragment::Fragment;
-pub use simple_element::SimpleElement;
-pub use text_element::Raw;
+pub use render_fn::RenderFn;
+pub use simple_element::{AttributeMap, SimpleElement};
+pub use style_list::StyleList;
+pub use text_element::{Raw, StaticHtml};
 pub use tide_jsx_impl::{component, html, rsx, view};
@@ -1,4 +1,7 @@
-use std::fmt::Result;
+#[cfg(debug_assertions)]
+use crate::content_model::RenderScope;
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter, Result};
 
 /// Render a component
 ///
@@ -8,14 +11,55 @@ pub trait Render: Sync + Send {
     /// Make sure you escape html correctly using the `render::html_escaping` module
     fn render_into(&self, writer: &mut String) -> Result;
 
-    /// Render the component to string
-    fn render(&self) -> String {
+    /// Render the component to string, or fail with the reason rendering stopped,
+    /// like a tag name picked at runtime that can't be written safely
+    fn try_render(&self) -> std::result::Result<String, RenderError> {
+        #[cfg(debug_assertions)]
+        let _scope = RenderScope::new();
+        FAILURE.with(|failure| failure.take());
         let mut buf = String::new();
-        self.render_into(&mut buf).unwrap();
-        buf
+        match self.render_into(&mut buf) {
+            Ok(()) => Ok(buf),
+            Err(fmt::Error) => Err(RenderError {
+                message: FAILURE
+                    .with(|failure| failure.take())
+                    .unwrap_or_else(|| "a component failed to render".to_string()),
+            }),
+        }
+    }
+
+    /// Render the component to string, panicking with the reason if it fails
+    fn render(&self) -> String {
+        self.try_render()
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 }
 
+thread_local! {
+    /// The reason of the last rendering failure on this thread, for `try_render`
+    static FAILURE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Stops rendering with a reason, which `Render::try_render` returns as a `RenderError`
+pub(crate) fn fail(message: String) -> fmt::Error {
+    FAILURE.with(|failure| *failure.borrow_mut() = Some(message));
+    fmt::Error
+}
+
+/// The reason rendering failed, returned by `Render::try_render`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderError {
+    message: String,
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "tide-jsx: {}", self.message)
+    }
+}
+
+impl std::error::Error for RenderError {}
+
 pub type BoxedRender = Box<dyn Render>;
 
 /// Does nothing
@@ -25,6 +69,20 @@ impl Render for () {
     }
 }
 
+/// Renders the referenced value
+impl<T: Render + ?Sized> Render for &T {
+    fn render_into(&self, writer: &mut String) -> Result {
+        (**self).render_into(writer)
+    }
+}
+
+/// Renders the boxed value, like a `BoxedRender` picked at runtime
+impl<T: Render + ?Sized> Render for Box<T> {
+    fn render_into(&self, writer: &mut String) -> Result {
+        (**self).render_into(writer)
+    }
+}
+
 /// Renders `A`, then `B`
 impl<A: Render, B: Render> Render for (A, B) {
     fn render_into(&self, writer: &mut String) -> Result {
@@ -0,0 +1,24 @@
+use crate::Render;
+use std::fmt::{Debug, Formatter, Result};
+
+/// Renders by calling a closure with the writer.
+///
+/// This is what `rsx!` generates for sibling nodes: every child is evaluated once,
+/// then written in order, keeping the generated type name small.
+#[derive(Clone)]
+pub struct RenderFn<F>(pub F);
+
+impl<F> Render for RenderFn<F>
+where
+    F: Fn(&mut String) -> Result + Send + Sync,
+{
+    fn render_into(&self, writer: &mut String) -> Result {
+        (self.0)(writer)
+    }
+}
+
+impl<F> Debug for RenderFn<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str("RenderFn")
+    }
+}
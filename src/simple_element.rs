@@ -1,15 +1,22 @@
+#[cfg(debug_assertions)]
+use crate::content_model;
 use crate::html_escaping::escape_html;
-use crate::Render;
+use crate::render::{self, Render};
+use indexmap::IndexMap;
 use std::borrow::Cow;
-use std::collections::HashMap;
 use std::fmt::{Result, Write};
 
-type Attributes<'a> = Option<HashMap<&'a str, Cow<'a, str>>>;
+/// The attributes of an element by name, written in insertion order
+pub type AttributeMap<'a> = IndexMap<Cow<'a, str>, Cow<'a, str>>;
+
+pub(crate) type Attributes<'a> = Option<AttributeMap<'a>>;
 
 /// Simple HTML element tag
 #[derive(Debug, Clone)]
 pub struct SimpleElement<'a, T: Render + Clone> {
     /// the HTML tag name, like `html`, `head`, `body`, `link`...
+    /// Rendering fails with a `RenderError` for names that aren't letters, digits
+    /// and dashes, since `<{tag}>` in `rsx!` takes them from runtime values.
     pub tag_name: &'a str,
     pub attributes: Attributes<'a>,
     pub contents: Option<T>,
@@ -19,10 +26,15 @@ fn write_attributes<W: Write>(maybe_attributes: &Attributes<'_>, writer: &mut W)
     match maybe_attributes {
         None => Ok(()),
         Some(attributes) => {
-            let mut attributes = attributes.clone();
-            for (key, value) in attributes.drain() {
+            for (key, value) in attributes {
+                // Names from spread attributes are only known at runtime, and can't
+                // be escaped, so invalid ones are left out
+                if !is_valid_attribute_name(key) {
+                    log::warn!("tide-jsx: invalid attribute name {:?} was left out", key);
+                    continue;
+                }
                 write!(writer, " {}=\"", key)?;
-                escape_html(&value, writer)?;
+                escape_html(value, writer)?;
                 write!(writer, "\"")?;
             }
             Ok(())
@@ -30,50 +42,80 @@ fn write_attributes<W: Write>(maybe_attributes: &Attributes<'_>, writer: &mut W)
     }
 }
 
+/// Elements that can't have any contents, rendered without a closing tag
+const VOID_ELEMENTS: &[&str] = tide_jsx_impl::void_elements!();
+
+/// Whether `tag_name` can be written in markup as is, like `h1` or `my-widget`
+fn is_valid_tag_name(tag_name: &str) -> bool {
+    let mut chars = tag_name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic())
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '-')
+}
+
+/// Whether `name` can be written as an attribute name, which excludes spaces,
+/// quotes, `>`, `/`, `=`, control characters and noncharacters
+fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|char| {
+            let is_noncharacter =
+                matches!(char, '\u{FDD0}'..='\u{FDEF}') || (char as u32 & 0xFFFE) == 0xFFFE;
+            !(char.is_control()
+                || is_noncharacter
+                || matches!(char, ' ' | '"' | '\'' | '>' | '/' | '='))
+        })
+}
+
 impl<T: Render + Clone> Render for SimpleElement<'_, T> {
     fn render_into(&self, writer: &mut String) -> Result {
-        match &self.contents {
-            None => {
-                if self.is_void_tag() {
-                    write!(writer, "<{}", self.tag_name)?;
-                    write_attributes(&self.attributes, writer)?;
-                    write!(writer, " />")
-                } else {
-                    write!(writer, "<{}", self.tag_name)?;
-                    write_attributes(&self.attributes, writer)?;
-                    write!(writer, "></{}>", self.tag_name)
-                }
-            }
-            Some(renderable) => {
-                write!(writer, "<{}", self.tag_name)?;
-                write_attributes(&self.attributes, writer)?;
-                write!(writer, ">")?;
-                renderable.render_into(writer)?;
-                write!(writer, "</{}>", self.tag_name)
-            }
-        }
+        let contents = self
+            .contents
+            .as_ref()
+            .map(|contents| contents as &dyn Render);
+        render_element(self.tag_name, &self.attributes, contents, writer)
     }
 }
 
-impl<T: Render + Clone> SimpleElement<'_, T> {
-    fn is_void_tag(&self) -> bool {
-        matches!(
-            self.tag_name,
-            "area"
-                | "base"
-                | "br"
-                | "col"
-                | "command"
-                | "embed"
-                | "hr"
-                | "img"
-                | "input"
-                | "link"
-                | "meta"
-                | "param"
-                | "source"
-                | "track"
-                | "wbr"
-        )
+/// Renders an element, failing for invalid tag names, and without contents or
+/// a closing tag for void elements
+pub(crate) fn render_element(
+    tag_name: &str,
+    attributes: &Attributes<'_>,
+    contents: Option<&dyn Render>,
+    writer: &mut String,
+) -> Result {
+    if !is_valid_tag_name(tag_name) {
+        return Err(render::fail(format!(
+            "invalid tag name {:?}. Tag names picked at runtime must start with a letter, followed by letters, digits and dashes",
+            tag_name
+        )));
+    }
+
+    #[cfg(debug_assertions)]
+    let _open_element = content_model::open_element(tag_name);
+    write!(writer, "<{}", tag_name)?;
+    write_attributes(attributes, writer)?;
+
+    if is_void_element(tag_name) {
+        if contents.is_some() {
+            log::warn!(
+                "tide-jsx: <{}> is a void element, so its contents were left out",
+                tag_name
+            );
+        }
+        return write!(writer, " />");
     }
+
+    write!(writer, ">")?;
+    if let Some(renderable) = contents {
+        renderable.render_into(writer)?;
+    }
+    write!(writer, "</{}>", tag_name)
+}
+
+fn is_void_element(tag_name: &str) -> bool {
+    VOID_ELEMENTS
+        .iter()
+        .any(|void_element| void_element.eq_ignore_ascii_case(tag_name))
 }
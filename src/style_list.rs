@@ -0,0 +1,133 @@
+use crate::AttributeValue;
+use std::borrow::Cow;
+
+/// A `style` attribute value, built from declarations in order. Setting a
+/// property again replaces its value, in its original position.
+///
+/// This is what `rsx!` generates to merge `style` with `style:property={..}` directives.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleList {
+    declarations: Vec<(String, String)>,
+}
+
+impl StyleList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds declarations written like a `style` attribute, as in `color: red; margin: 0`
+    pub fn push(&mut self, style: &str) {
+        for declaration in split_declarations(style) {
+            if let Some((property, value)) = declaration.split_once(':') {
+                self.set(property.trim(), value.trim());
+            }
+        }
+    }
+
+    /// Sets a property, unless its value leaves the attribute out, like `None`.
+    /// Values are cut at their first `;` outside of quotes and parentheses, so they
+    /// can't add other declarations.
+    pub fn set<'a>(&mut self, property: &str, value: impl AttributeValue<'a>) {
+        let value = match value.into_attribute_value() {
+            Some(value) => value,
+            None => return,
+        };
+        let declaration_value = split_declarations(&value).next().unwrap_or_default();
+        if declaration_value.len() < value.len() {
+            log::warn!(
+                "tide-jsx: the value of the {:?} style property was cut at its first `;`: {:?}",
+                property,
+                value
+            );
+        }
+        let value = match declaration_value.trim() {
+            "" => return,
+            value => value.to_string(),
+        };
+
+        match self
+            .declarations
+            .iter_mut()
+            .find(|(existing, _)| existing == property)
+        {
+            Some((_, existing_value)) => *existing_value = value,
+            None => self.declarations.push((property.to_string(), value)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+}
+
+/// Splits a `style` attribute on the `;` ending each declaration, skipping the ones
+/// in quotes and parentheses, like in `url("data:image/png;base64,...")`
+fn split_declarations(style: &str) -> impl Iterator<Item = &str> {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut ends = Vec::new();
+
+    for (index, char) in style.char_indices() {
+        match (quote, char) {
+            _ if escaped => escaped = false,
+            (_, '\\') => escaped = true,
+            (Some(open), _) if char == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(char),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                ends.push((start, index));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    ends.push((start, style.len()));
+
+    ends.into_iter().map(move |(start, end)| &style[start..end])
+}
+
+impl<'a> AttributeValue<'a> for StyleList {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        (&self).into_attribute_value()
+    }
+}
+
+impl<'a> AttributeValue<'a> for &StyleList {
+    fn into_attribute_value(self) -> Option<Cow<'a, str>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let style = self
+            .declarations
+            .iter()
+            .map(|(property, value)| format!("{}: {}", property, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Some(Cow::Owned(style))
+    }
+}
+
+/// Builds a [`StyleList`] from `"property": value` pairs. Properties whose value
+/// would leave an attribute out, like `None`, are skipped.
+///
+/// ```
+/// use tide_jsx::{html, styles};
+///
+/// let color: Option<&str> = None;
+/// let margin = 4;
+/// let style = styles! { "color": color, "margin-top": format!("{}px", margin) };
+/// assert_eq!(html! { <div style={style} /> }, r#"<div style="margin-top: 4px"></div>"#);
+/// ```
+#[macro_export]
+macro_rules! styles {
+    ($($property:literal : $value:expr),* $(,)?) => {{
+        let mut list = $crate::StyleList::new();
+        $(list.set($property, $value);)*
+        list
+    }};
+}
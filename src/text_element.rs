@@ -1,10 +1,12 @@
+#[cfg(debug_assertions)]
+use crate::content_model;
 use crate::html_escaping::escape_html;
 use crate::Render;
-use std::fmt::{Result, Write};
+use std::fmt::Result;
 
 impl Render for String {
     fn render_into(&self, writer: &mut String) -> Result {
-        escape_html(&self, writer)
+        escape_html(self, writer)
     }
 }
 
@@ -16,7 +18,7 @@ impl Render for &str {
 
 impl Render for std::borrow::Cow<'_, str> {
     fn render_into(&self, writer: &mut String) -> Result {
-        escape_html(&self, writer)
+        escape_html(self, writer)
     }
 }
 
@@ -33,7 +35,39 @@ impl<'s> From<&'s str> for Raw<'s> {
 /// A raw (unencoded) html string
 impl<'s> Render for Raw<'s> {
     fn render_into(&self, writer: &mut String) -> Result {
-        write!(writer, "{}", self.0)
+        writer.push_str(self.0);
+        Ok(())
+    }
+}
+
+/// Markup rendered by `rsx!` at compile time, with the elements it contains in
+/// document order and their depth, whose nesting is checked while rendering in
+/// debug builds like the elements rendered at runtime
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct StaticHtml {
+    html: &'static str,
+    #[cfg(debug_assertions)]
+    elements: &'static [(usize, &'static str)],
+}
+
+impl StaticHtml {
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    pub const fn new(html: &'static str, elements: &'static [(usize, &'static str)]) -> Self {
+        StaticHtml {
+            html,
+            #[cfg(debug_assertions)]
+            elements,
+        }
+    }
+}
+
+impl Render for StaticHtml {
+    fn render_into(&self, writer: &mut String) -> Result {
+        #[cfg(debug_assertions)]
+        content_model::check_static_elements(self.elements);
+        writer.push_str(self.html);
+        Ok(())
     }
 }
 
@@ -2,7 +2,9 @@ use pretty_assertions::assert_eq;
 use std::borrow::Cow;
 use tide::StatusCode;
 use tide_jsx::html::HTML5Doctype;
-use tide_jsx::{component, html, raw, rsx, view, Render};
+use tide_jsx::{
+    classes, component, html, raw, rsx, styles, view, ClassList, ConditionalComment, Render,
+};
 
 #[test]
 fn ui() {
@@ -15,7 +17,715 @@ fn works_with_dashes() {
     use pretty_assertions::assert_eq;
 
     let value = html! { <div data-id={"myid"} /> };
-    assert_eq!(value, r#"<div data-id="myid"/>"#);
+    assert_eq!(value, r#"<div data-id="myid"></div>"#);
+}
+
+#[test]
+fn works_with_data_aria_and_framework_attributes() {
+    assert_eq!(
+        html! { <button aria-label={"Close"} /> },
+        r#"<button aria-label="Close"></button>"#
+    );
+    assert_eq!(
+        html! { <div hx-get={"/items"} /> },
+        r#"<div hx-get="/items"></div>"#
+    );
+}
+
+#[test]
+fn works_with_namespaced_and_framework_attributes() {
+    assert_eq!(
+        html! { <use xlink:href={"#icon"} /> },
+        r##"<use xlink:href="#icon"></use>"##
+    );
+    assert_eq!(
+        html! { <button @click={"open = true"} /> },
+        r#"<button @click="open = true"></button>"#
+    );
+    assert_eq!(
+        html! { <button hx-on:click={"go()"} /> },
+        r#"<button hx-on:click="go()"></button>"#
+    );
+    assert_eq!(
+        html! { <div hx-on::after-request={"done()"} /> },
+        r#"<div hx-on::after-request="done()"></div>"#
+    );
+    assert_eq!(
+        html! { <form x-on:submit.prevent={"save"} /> },
+        r#"<form x-on:submit.prevent="save"></form>"#
+    );
+    assert_eq!(
+        html! { <div :class={"{ busy }"} /> },
+        r#"<div :class="{ busy }"></div>"#
+    );
+    assert_eq!(
+        html! { <div "@keyup.shift.enter"={"send"} /> },
+        r#"<div @keyup.shift.enter="send"></div>"#
+    );
+}
+
+#[test]
+fn works_with_custom_elements() {
+    let variant = "primary";
+    assert_eq!(
+        html! { <sl-button variant={variant} size="small">{"Save"}</sl-button> },
+        r#"<sl-button variant="primary" size="small">Save</sl-button>"#
+    );
+    assert_eq!(
+        html! { <my-app-shell><my-widget /></my-app-shell> },
+        "<my-app-shell><my-widget></my-widget></my-app-shell>"
+    );
+}
+
+#[test]
+fn works_with_number_and_boolean_literals() {
+    assert_eq!(
+        html! { <td colspan=2>{"Total"}</td> },
+        r#"<td colspan="2">Total</td>"#
+    );
+    assert_eq!(
+        html! { <div tabindex=-1 /> },
+        r#"<div tabindex="-1"></div>"#
+    );
+    assert_eq!(
+        html! { <meter value=0.75 /> },
+        r#"<meter value="0.75"></meter>"#
+    );
+    assert_eq!(html! { <div hidden=true /> }, r#"<div hidden=""></div>"#);
+    assert_eq!(html! { <div hidden=false /> }, "<div></div>");
+
+    let label = "Save";
+    assert_eq!(
+        html! { <button disabled=true>{label}</button> },
+        r#"<button disabled="">Save</button>"#
+    );
+    assert_eq!(
+        html! { <button disabled=false>{label}</button> },
+        "<button>Save</button>"
+    );
+
+    // Other attributes write `true` and `false` as they are
+    assert_eq!(
+        html! { <div aria-hidden=true spellcheck=false draggable=true /> },
+        r#"<div aria-hidden="true" spellcheck="false" draggable="true"></div>"#
+    );
+    assert_eq!(
+        html! { <button aria-expanded=false contenteditable=true>{label}</button> },
+        r#"<button aria-expanded="false" contenteditable="true">Save</button>"#
+    );
+}
+
+#[test]
+fn works_with_bare_boolean_attributes() {
+    assert_eq!(
+        html! { <input type="checkbox" checked disabled aria-label="Done" /> },
+        r#"<input type="checkbox" checked="" disabled="" aria-label="Done" />"#
+    );
+
+    let label = "Save";
+    assert_eq!(
+        html! { <button disabled>{label}</button> },
+        r#"<button disabled="">Save</button>"#
+    );
+
+    let class = "primary";
+    assert_eq!(
+        html! { <button class>{label}</button> },
+        r#"<button class="primary">Save</button>"#
+    );
+}
+
+#[test]
+fn works_with_spread_attributes() {
+    let extra = vec![("data-id", "42")];
+    assert_eq!(html! { <div {..extra} /> }, r#"<div data-id="42"></div>"#);
+
+    let overridden = vec![("type", "email")];
+    assert_eq!(
+        html! { <input {..overridden} type="text" /> },
+        r#"<input type="text" />"#
+    );
+
+    // Explicit attributes without a value remove the spread ones
+    let spread = vec![("disabled", true), ("hidden", true), ("inert", true)];
+    assert_eq!(
+        html! { <button {..spread.clone()} disabled=false hidden={false} inert={false}! /> },
+        "<button></button>"
+    );
+    let titled = vec![("title", "Spread"), ("class", "spread")];
+    assert_eq!(
+        html! { <p {..titled} title={None::<&str>} class:active={false}>{"Hi"}</p> },
+        "<p>Hi</p>"
+    );
+
+    let mut forwarded = std::collections::HashMap::new();
+    forwarded.insert(String::from("title"), String::from("Greeting"));
+    assert_eq!(
+        html! { <p {..&forwarded}>{"Hello"}</p> },
+        r#"<p title="Greeting">Hello</p>"#
+    );
+
+    #[component]
+    fn TextInput<'a>(attributes: &'a [(&'a str, Option<&'a str>)]) {
+        rsx! { <input {..attributes.iter().copied()} /> }
+    }
+
+    let attributes = [("name", Some("email")), ("placeholder", None)];
+    assert_eq!(
+        html! { <TextInput attributes={&attributes} /> },
+        r#"<input name="email" />"#
+    );
+
+    // Names that can't be written safely are left out
+    let untrusted = vec![
+        ("onmouseover=alert(1) x", "y"),
+        ("a\"b", "c"),
+        ("", "empty"),
+        ("data-ok", "1"),
+    ];
+    assert_eq!(
+        html! { <div {..untrusted} /> },
+        r#"<div data-ok="1"></div>"#
+    );
+}
+
+#[test]
+fn attributes_keep_their_order() {
+    let id = "save";
+    let is_active = true;
+    assert_eq!(
+        html! { <button type="submit" class="btn" id={id} class:active={is_active} data-x="1" /> },
+        r#"<button type="submit" class="btn active" id="save" data-x="1"></button>"#
+    );
+
+    // Spread attributes come first, and explicit ones override them in place
+    let spread = vec![("title", "Save"), ("type", "button")];
+    assert_eq!(
+        html! { <button id={id} {..spread} type="submit">{"Save"}</button> },
+        r#"<button title="Save" type="submit" id="save">Save</button>"#
+    );
+}
+
+#[test]
+fn works_with_class_and_style_directives() {
+    let is_active = true;
+    let is_disabled = false;
+    assert_eq!(
+        html! { <li class="item active" class:active={is_active} class:disabled={is_disabled} class:last=true /> },
+        r#"<li class="item active last"></li>"#
+    );
+    assert_eq!(html! { <li class:active={is_disabled} /> }, "<li></li>");
+
+    let color = "red";
+    let width: Option<&str> = None;
+    assert_eq!(
+        html! { <p style="color: blue; margin: 0" style:color={color} style:width={width}>{color}</p> },
+        r#"<p style="color: red; margin: 0">red</p>"#
+    );
+
+    // Semicolons in quotes and parentheses don't end a declaration
+    assert_eq!(
+        html! { <div style="background: url(\"data:image/png;base64,AA==\"); color: blue" style:color={color} /> },
+        r#"<div style="background: url(&quot;data:image/png;base64,AA==&quot;); color: red"></div>"#
+    );
+    assert_eq!(
+        html! { <div style="content: ';'; mask: url(data:image/svg+xml;utf8,x)" style:color={color} /> },
+        r#"<div style="content: &apos;;&apos;; mask: url(data:image/svg+xml;utf8,x); color: red"></div>"#
+    );
+
+    // Directive values can't add other declarations
+    let injected = "red; position: fixed";
+    assert_eq!(
+        html! { <p style="position: static" style:color={injected} style:margin={";"} /> },
+        r#"<p style="position: static; color: red"></p>"#
+    );
+    assert_eq!(
+        html! { <p style={styles! { "background": "url(\"a;b\"); color: red" }} /> },
+        r#"<p style="background: url(&quot;a;b&quot;)"></p>"#
+    );
+}
+
+#[test]
+fn works_with_class_and_id_shorthand() {
+    assert_eq!(
+        html! { <div.card.shadow #main>{"Hi"}</div> },
+        r#"<div class="card shadow" id="main">Hi</div>"#
+    );
+    assert_eq!(
+        html! { <section.mt-4.card.mt-4 class="card wide" /> },
+        r#"<section class="mt-4 card wide"></section>"#
+    );
+    assert_eq!(
+        html! { <input #email.field type="email" aria-label="Email" /> },
+        r#"<input id="email" class="field" type="email" aria-label="Email" />"#
+    );
+
+    let is_active = true;
+    assert_eq!(
+        html! { <li.item class:active={is_active} class:hidden={!is_active} /> },
+        r#"<li class="item active"></li>"#
+    );
+
+    let extra = "wide";
+    assert_eq!(
+        html! { <div.card class={extra} /> },
+        r#"<div class="card wide"></div>"#
+    );
+}
+
+#[test]
+fn works_with_class_and_style_builders() {
+    let primary = true;
+    let extra: Option<String> = Some(String::from("wide btn"));
+    let missing: Option<&str> = None;
+    assert_eq!(
+        html! { <button class={classes!["btn", "btn-primary" => primary, "btn-link" => !primary, extra, missing]} /> },
+        r#"<button class="btn btn-primary wide"></button>"#
+    );
+    assert_eq!(html! { <div class={classes![]} /> }, "<div></div>");
+
+    let margin = 4;
+    let color: Option<&str> = None;
+    assert_eq!(
+        html! { <div style={styles! { "margin-top": format!("{}px", margin), "color": color, "opacity": 0.5 }} /> },
+        r#"<div style="margin-top: 4px; opacity: 0.5"></div>"#
+    );
+    assert_eq!(
+        html! { <div title={styles! { "content": "\"<>\"" }} /> },
+        r#"<div title="content: &quot;&lt;&gt;&quot;"></div>"#
+    );
+
+    #[component]
+    fn Badge(class: ClassList) {
+        rsx! { <span class={class} /> }
+    }
+
+    assert_eq!(
+        html! { <Badge class={classes!["badge", "badge-new" => true]} /> },
+        r#"<span class="badge badge-new"></span>"#
+    );
+}
+
+#[test]
+fn works_with_typed_attribute_values() {
+    let rows: u32 = 3;
+    assert_eq!(
+        html! { <textarea rows={rows} /> },
+        r#"<textarea rows="3"></textarea>"#
+    );
+
+    let title = String::from("Greeting");
+    let maybe_title = Some(&title);
+    assert_eq!(
+        html! { <div title={maybe_title} /> },
+        r#"<div title="Greeting"></div>"#
+    );
+    assert_eq!(html! { <div title={None::<&str>} /> }, "<div></div>");
+
+    let selected = false;
+    assert_eq!(
+        html! { <option selected={selected}>{rows}</option> },
+        "<option>3</option>"
+    );
+    assert_eq!(
+        html! { <option selected={!selected}>{rows}</option> },
+        r#"<option selected="">3</option>"#
+    );
+
+    // Other attributes write booleans as they are
+    let is_pressed = false;
+    assert_eq!(
+        html! { <button aria-pressed={is_pressed} disabled={is_pressed}>{rows}</button> },
+        r#"<button aria-pressed="false">3</button>"#
+    );
+    let expanded = Some(true);
+    assert_eq!(
+        html! { <details open={expanded} aria-expanded={expanded}>{rows}</details> },
+        r#"<details open="" aria-expanded="true">3</details>"#
+    );
+
+    let spread = vec![("hidden", false), ("draggable", false), ("Inert", true)];
+    assert_eq!(
+        html! { <div {..spread} /> },
+        r#"<div draggable="false" Inert=""></div>"#
+    );
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn works_with_url_attribute_values() {
+    let url = url::Url::parse("https://example.com/search?q=a&b").unwrap();
+    assert_eq!(
+        html! { <a href={&url}>{"Search"}</a> },
+        r#"<a href="https://example.com/search?q=a&amp;b">Search</a>"#
+    );
+    assert_eq!(
+        html! { <link rel="canonical" href={url} /> },
+        r#"<link rel="canonical" href="https://example.com/search?q=a&amp;b" />"#
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn works_with_uuid_attribute_values() {
+    let id = uuid::Uuid::nil();
+    assert_eq!(
+        html! { <div data-id={id} /> },
+        r#"<div data-id="00000000-0000-0000-0000-000000000000"></div>"#
+    );
+}
+
+#[test]
+fn works_with_dynamic_tag_names() {
+    #[component]
+    fn Heading<'a>(level: u8, title: &'a str) {
+        let tag = match level {
+            1 => "h1",
+            2 => "h2",
+            _ => "h3",
+        };
+        rsx! { <{tag} class="title">{title}</{tag}> }
+    }
+
+    assert_eq!(
+        html! { <Heading level={2} title={"Hello"} /> },
+        r#"<h2 class="title">Hello</h2>"#
+    );
+
+    let tag = "br";
+    assert_eq!(html! { <{tag}>{"ignored"}</{tag}> }, "<br />");
+
+    let tag = "img onerror=alert(1)";
+    let error = rsx! { <div><{tag} /></div> }.try_render().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        concat!(
+            r#"tide-jsx: invalid tag name "img onerror=alert(1)". "#,
+            "Tag names picked at runtime must start with a letter, followed by letters, digits and dashes"
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = r#"tide-jsx: invalid tag name "1h""#)]
+fn render_panics_with_the_reason() {
+    let tag = "1h";
+    html! { <{tag} /> };
+}
+
+#[test]
+fn works_with_enum_variant_components() {
+    #[derive(Debug, Clone)]
+    enum Widget<'a> {
+        Chart { title: &'a str },
+        Table { rows: usize },
+    }
+
+    impl Render for Widget<'_> {
+        fn render_into(&self, writer: &mut String) -> std::fmt::Result {
+            match self {
+                Widget::Chart { title } => rsx! { <figure>{*title}</figure> }.render_into(writer),
+                Widget::Table { rows } => rsx! { <table data-rows={*rows} /> }.render_into(writer),
+            }
+        }
+    }
+
+    assert_eq!(
+        html! {
+          <div>
+            <Widget::Chart title={"Sales"}></Widget::Chart>
+            <Widget::Table rows={3}></Widget::Table>
+          </div>
+        },
+        r#"<div><figure>Sales</figure><table data-rows="3"></table></div>"#
+    );
+}
+
+#[test]
+fn works_with_dynamic_components() {
+    use std::fmt;
+    use tide_jsx::{BoxedRender, DynamicProps};
+
+    #[component]
+    fn Chart(title: String) {
+        rsx! { <figure><figcaption>{title}</figcaption></figure> }
+    }
+
+    fn chart(props: DynamicProps, writer: &mut String) -> fmt::Result {
+        let title = props.attribute("title").unwrap_or_default().to_string();
+        Chart { title }.render_into(writer)
+    }
+
+    #[component]
+    fn Note<Children: Render>(children: Children) {
+        rsx! { <aside>{children}</aside> }
+    }
+
+    fn note(props: DynamicProps, writer: &mut String) -> fmt::Result {
+        rsx! { <Note>{props.children()}</Note> }.render_into(writer)
+    }
+
+    enum WidgetKind {
+        Chart,
+        Note,
+    }
+
+    impl WidgetKind {
+        fn component(&self) -> fn(DynamicProps, &mut String) -> fmt::Result {
+            match self {
+                WidgetKind::Chart => chart,
+                WidgetKind::Note => note,
+            }
+        }
+    }
+
+    let props = vec![("title", "Sales")];
+    assert_eq!(
+        html! { <{WidgetKind::Chart.component()} {..props} /> },
+        "<figure><figcaption>Sales</figcaption></figure>"
+    );
+    assert_eq!(
+        html! { <{WidgetKind::Note.component()}><b>{"Hi"}</b></{WidgetKind::Note.component()}> },
+        "<aside><b>Hi</b></aside>"
+    );
+
+    let widgets: Vec<BoxedRender> = vec![Box::new(Chart {
+        title: "Traffic".to_string(),
+    })];
+    assert_eq!(
+        html! { <section>{&widgets[0]}</section> },
+        "<section><figure><figcaption>Traffic</figcaption></figure></section>"
+    );
+}
+
+#[test]
+fn works_with_multiple_root_nodes() {
+    assert_eq!(
+        html! { <dt>{"Term"}</dt><dd>{"Details"}</dd> },
+        "<dt>Term</dt><dd>Details</dd>"
+    );
+
+    let name = "Ferris";
+    assert_eq!(
+        html! { {"Hello, "}<b>{name}</b>{"!"} },
+        "Hello, <b>Ferris</b>!"
+    );
+    assert_eq!(html! { {name} }, "Ferris");
+
+    let rows = rsx! {
+        <tr><td>{1}</td></tr>
+        <tr><td>{2}</td></tr>
+    };
+    assert_eq!(
+        html! { <table><tbody>{rows}</tbody></table> },
+        "<table><tbody><tr><td>1</td></tr><tr><td>2</td></tr></tbody></table>"
+    );
+
+    // Empty markup renders nothing
+    let empty = rsx! {};
+    assert_eq!(html! { <p>{empty}<></></p> }, "<p></p>");
+    assert_eq!(html! {}, "");
+}
+
+#[test]
+fn works_with_let_bindings() {
+    #[component]
+    fn Cart<'a>(items: &'a [(&'a str, u32)]) {
+        rsx! {
+            <section>
+                @let count = items.len();
+                @let (first, _) = items[0];
+                <h2>{count}{" items"}</h2>
+                <p>{"From "}{first}{", "}{count}{" in total"}</p>
+            </section>
+        }
+    }
+
+    assert_eq!(
+        html! { <Cart items={&[("apples", 3), ("pears", 2)]} /> },
+        "<section><h2>2 items</h2><p>From apples, 2 in total</p></section>"
+    );
+
+    let price = 12;
+    assert_eq!(
+        html! {
+            @let total: u32 = price * 2;
+            <b>{total}</b>
+            @let total = total + 1;
+            <i>{total}</i>
+        },
+        "<b>24</b><i>25</i>"
+    );
+
+    // Bindings can be borrowed and reused by the following siblings
+    let name = "Ferris";
+    let title = String::from("Crabs");
+    assert_eq!(
+        html! {
+            <ul>
+                <li>{name}</li>
+                @let label = format!("{} the crab", name);
+                <li>{label.as_str()}</li>
+                <li>{&label}</li>
+                <li>{label.clone()}</li>
+                <li>{&title}</li>
+            </ul>
+        },
+        "<ul><li>Ferris</li><li>Ferris the crab</li><li>Ferris the crab</li><li>Ferris the crab</li><li>Crabs</li></ul>"
+    );
+}
+
+#[test]
+fn works_with_character_references() {
+    assert_eq!(
+        html! { <p>&copy;&nbsp;{"2024"}&#8212;&#x2014;&in;</p> },
+        "<p>&copy;&nbsp;2024&#8212;&#x2014;&in;</p>"
+    );
+
+    let name = "Ferris";
+    assert_eq!(
+        html! { <p>{name}&nbsp;&mdash;&nbsp;<b>{"crab"}</b></p> },
+        "<p>Ferris&nbsp;&mdash;&nbsp;<b>crab</b></p>"
+    );
+}
+
+#[test]
+fn works_with_text_and_whitespace() {
+    let a = "a";
+    let b = "b";
+    assert_eq!(
+        html! { <p><span>{a}</span><_ /><span>{b}</span></p> },
+        "<p><span>a</span> <span>b</span></p>"
+    );
+    // Whitespace between nodes on a line is a single space
+    assert_eq!(
+        html! { <p><span>{a}</span> <span>{b}</span>   {a}&nbsp;<i>{b}</i></p> },
+        "<p><span>a</span> <span>b</span> a&nbsp;<i>b</i></p>"
+    );
+    assert_eq!(
+        html! { <p><span>{a}</span>" "<span>{b}</span></p> },
+        "<p><span>a</span> <span>b</span></p>"
+    );
+    // Whitespace with line breaks is dropped
+    assert_eq!(
+        html! {
+            <ul>
+                <li>{a}</li>
+                <li>{b}</li>
+            </ul>
+        },
+        "<ul><li>a</li><li>b</li></ul>"
+    );
+
+    let name = "Ferris";
+    assert_eq!(
+        html! { <p>"Hello, " <b>{name}</b>"! <3"</p> },
+        "<p>Hello, <b>Ferris</b>! &lt;3</p>"
+    );
+    assert_eq!(
+        html! {
+            <p>
+                "A long sentence,
+                 split over   two lines.
+
+                "
+            </p>
+        },
+        "<p>A long sentence, split over   two lines.</p>"
+    );
+
+    assert_eq!(
+        html! {
+            <pre>"fn main() {\n    run();\n}"</pre>
+        },
+        "<pre>fn main() {\n    run();\n}</pre>"
+    );
+    // Literals in blocks follow the same rules as bare ones
+    assert_eq!(
+        html! {
+            <p>{"a
+                  b"} "a
+                       b"</p>
+        },
+        "<p>a b a b</p>"
+    );
+    // Preserved whitespace between nodes is kept as written, with indentation
+    // written as spaces
+    assert_eq!(
+        html! {
+            <pre>
+                <b>{"fn"}</b>  {"main() {"}
+                  <i>{"run();"}</i>
+                {"}"}
+            </pre>
+        },
+        concat!(
+            "<pre><b>fn</b>  main() {\n",
+            "                  <i>run();</i>\n",
+            "                }</pre>"
+        )
+    );
+    assert_eq!(
+        html! {
+            <div>
+                #[whitespace(preserve)]
+                <code>"a\n  b"</code>
+                "c
+                 d"
+            </div>
+        },
+        "<div><code>a\n  b</code>c d</div>"
+    );
+}
+
+#[cfg(not(feature = "strip-comments"))]
+#[test]
+fn works_with_comments() {
+    assert_eq!(
+        html! { <div><!-- "Sidebar" --><p>{"Hi"}</p></div> },
+        "<div><!-- Sidebar --><p>Hi</p></div>"
+    );
+
+    let note = format!("Built at {}", "--noon-->");
+    assert_eq!(
+        html! { <div><!-- {note} --></div> },
+        "<div><!-- Built at - -noon- -> --></div>"
+    );
+}
+
+#[cfg(feature = "strip-comments")]
+#[test]
+fn strips_comments() {
+    let note = "Sidebar";
+    assert_eq!(
+        html! { <div><!-- "Sidebar" --><!-- {note} --><p>{"Hi"}</p></div> },
+        "<div><p>Hi</p></div>"
+    );
+}
+
+#[test]
+fn works_with_conditional_comments() {
+    assert_eq!(
+        html! {
+            <body>
+                <!--[if gte mso 9]><table><tr><td>"Outlook"</td></tr></table><![endif]-->
+                <!--[if !mso]>"Other clients"<![endif]-->
+            </body>
+        },
+        "<body><!--[if gte mso 9]><table><tr><td>Outlook</td></tr></table><![endif]--><!--[if !mso]>Other clients<![endif]--></body>"
+    );
+
+    let width = 600;
+    assert_eq!(
+        html! { <div><!--[if "(gt IE 5)&(lt IE 7)"]><table data-width={width} /><![endif]--></div> },
+        "<div><!--[if (gt IE 5)&(lt IE 7)]><table data-width=\"600\"></table><![endif]--></div>"
+    );
+
+    let invalid = ConditionalComment::new("mso]><script>", Option::<()>::None);
+    assert_eq!(
+        invalid.try_render().unwrap_err().to_string(),
+        "tide-jsx: invalid conditional comment condition \"mso]><script>\""
+    );
 }
 
 #[test]
@@ -30,16 +740,23 @@ fn works_with_raw() {
 #[test]
 fn works_with_raw_ident() {
     let actual = html! {
+        #![allow(missing_label)]
         <input r#type={"text"} />
     };
 
-    assert_eq!(actual, r#"<input type="text"/>"#);
+    assert_eq!(actual, r#"<input type="text" />"#);
 }
 
 #[test]
 fn works_with_keywords() {
-    assert_eq!(html! { <input type={"text"} /> }, r#"<input type="text"/>"#);
-    assert_eq!(html! { <label for={"me"} /> }, r#"<label for="me"/>"#);
+    assert_eq!(
+        html! { #![allow(missing_label)] <input type={"text"} /> },
+        r#"<input type="text" />"#
+    );
+    assert_eq!(
+        html! { <label for={"me"} /> },
+        r#"<label for="me"></label>"#
+    );
 }
 
 #[test]
@@ -68,7 +785,154 @@ fn element_ordering() {
 
     assert_eq!(
         deep,
-        "<div><h1>A list</h1><hr/><ul><li>1</li><li>2</li><li>3</li></ul></div>"
+        "<div><h1>A list</h1><hr /><ul><li>1</li><li>2</li><li>3</li></ul></div>"
+    );
+}
+
+#[test]
+fn void_elements_without_slash() {
+    let actual = html! {
+      #![allow(missing_label)]
+      <form>
+        <input type={"text"}>
+        <br>
+        <label for={"me"}>{"Me"}</label>
+      </form>
+    };
+
+    assert_eq!(
+        actual,
+        r#"<form><input type="text" /><br /><label for="me">Me</label></form>"#
+    );
+}
+
+#[test]
+fn accessible_markup() {
+    let actual = html! {
+      #![deny(a11y)]
+      <form role={"search"}>
+        <h2>{"Search"}</h2>
+        <label>{"Query"}<input type={"search"} /></label>
+        <img alt={""} />
+        <h3 aria-live={"polite"}>{"Results"}</h3>
+      </form>
+    };
+
+    assert_eq!(
+        actual,
+        concat!(
+            r#"<form role="search"><h2>Search</h2>"#,
+            r#"<label>Query<input type="search" /></label>"#,
+            r#"<img alt="" /><h3 aria-live="polite">Results</h3></form>"#
+        )
+    );
+}
+
+#[test]
+fn sibling_nodes() {
+    let child_0 = "first";
+    let writer = String::from("second");
+
+    assert_eq!(
+        html! { <p>{child_0}<br />{writer}</p> },
+        "<p>first<br />second</p>"
+    );
+}
+
+#[test]
+fn static_subtrees() {
+    let folded: tide_jsx::StaticHtml = rsx! { <ul><li>{"1"}</li><li>{"2"}</li></ul> };
+    assert_eq!(folded.render(), "<ul><li>1</li><li>2</li></ul>");
+
+    let name = "<Gal>";
+    let actual = html! {
+      <ul class={"list"}>
+        <li>{"Tom & Jerry"}</li>
+        <li>{name}</li>
+        <li><a href={"/?a=1&b=2"}>{"link"}</a></li>
+      </ul>
+    };
+
+    assert_eq!(
+        actual,
+        concat!(
+            r#"<ul class="list"><li>Tom &amp; Jerry</li><li>&lt;Gal&gt;</li>"#,
+            r#"<li><a href="/?a=1&amp;b=2">link</a></li></ul>"#
+        )
+    );
+}
+
+/// Collects the warnings logged by tide-jsx while running `f` on this thread
+#[cfg(debug_assertions)]
+fn logged_warnings(f: impl FnOnce()) -> Vec<String> {
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    thread_local! {
+        static WARNINGS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    }
+
+    struct Logger;
+
+    impl log::Log for Logger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Warn
+        }
+
+        fn log(&self, record: &log::Record) {
+            WARNINGS.with(|warnings| {
+                if let Some(warnings) = warnings.borrow_mut().as_mut() {
+                    warnings.push(record.args().to_string());
+                }
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&Logger).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+    });
+
+    WARNINGS.with(|warnings| *warnings.borrow_mut() = Some(Vec::new()));
+    f();
+    WARNINGS.with(|warnings| warnings.borrow_mut().take().unwrap())
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn checks_nesting_across_components_while_rendering() {
+    #[component]
+    fn Card() {
+        rsx! { <div class="card"><h2>{"Title"}</h2></div> }
+    }
+
+    #[component]
+    fn More() {
+        rsx! { <a href="/more">{"More"}</a> }
+    }
+
+    // Runtime components render their children with the elements around them
+    fn paragraph(props: tide_jsx::DynamicProps, writer: &mut String) -> std::fmt::Result {
+        rsx! { <p>{props.children()}</p> }.render_into(writer)
+    }
+
+    let warnings = logged_warnings(|| {
+        html! { <p><Card /></p> };
+        html! { <a href="/"><span><More /></span></a> };
+        html! { <section><Card /></section> };
+        html! { <a href="/"><{paragraph}><More /></{paragraph}></a> };
+    });
+
+    assert_eq!(
+        warnings,
+        [
+            "tide-jsx: invalid nesting: <div> can't be a child of <p>. Browsers close the <p> before it",
+            "tide-jsx: invalid nesting: <a> can't be nested inside <a>",
+            "tide-jsx: invalid nesting: <a> can't be nested inside <a>",
+        ]
     );
 }
 
@@ -150,6 +1014,14 @@ fn vec() {
     )
 }
 
+#[test]
+fn view_fails_with_a_server_error() {
+    let tag = "script src=//evil";
+    let error = (view! { <{tag} /> } as tide::Result).unwrap_err();
+    assert_eq!(error.status(), StatusCode::InternalServerError);
+    assert!(error.to_string().contains("invalid tag name"));
+}
+
 #[async_std::test]
 async fn render_view() -> std::io::Result<()> {
     let result = view! { <p>{"hello"}</p> } as tide::Result;
@@ -202,6 +1074,35 @@ mod kaki {
         assert_eq!(actual, expected);
     }
 
+    // Compiled once, whatever the children are
+    #[component(dyn)]
+    fn Layout<'a, Children: Render>(title: &'a str, children: Children) {
+        rsx! {
+          <html>
+            <head><title>{title}</title></head>
+            <body>{children}</body>
+          </html>
+        }
+    }
+
+    #[test]
+    fn type_erased_test() {
+        let name = "Gal";
+        let actual = html! {
+          <Layout title={"Home"}>
+            <h1>{"Welcome"}</h1>
+            {name}
+          </Layout>
+        };
+        let expected = concat!(
+            "<html>",
+            "<head><title>Home</title></head>",
+            "<body><h1>Welcome</h1>Gal</body>",
+            "</html>"
+        );
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn externals_test() {
         let actual = html! {
@@ -227,7 +1128,6 @@ mod kaki {
 /// ## Other
 ///
 /// Module for testing component visibility when imported from other modules.
-
 mod other {
     use crate::{component, rsx, HTML5Doctype, Render};
 
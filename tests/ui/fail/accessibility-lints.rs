@@ -0,0 +1,15 @@
+use tide_jsx::html;
+
+fn main() {
+    html! {
+        #![deny(a11y)]
+        <main role={"mian"}>
+            <h1>{"Title"}</h1>
+            <h3>{"Skipped a level"}</h3>
+            <img src={"/logo.png"} />
+            <input type={"text"} />
+            <a onclick={"open()"}>{"Open"}</a>
+            <div aria-lable={"Oops"} />
+        </main>
+    };
+}
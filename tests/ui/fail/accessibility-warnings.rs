@@ -0,0 +1,11 @@
+#![deny(deprecated)]
+
+use tide_jsx::html;
+
+fn main() {
+    html! { <img src={"/logo.png"} /> };
+    html! { #![allow(missing_alt)] <img src={"/spacer.gif"} /> };
+    html! { #![allow(alt)] <img alt={""} /> };
+    html! { <div><li>{"item"}</li></div> };
+    html! { #![allow(invalid_nesting)] <button><a href={"/"}>{"Home"}</a></button> };
+}
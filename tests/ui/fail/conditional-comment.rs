@@ -0,0 +1,8 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <div><!--[if "mso]>"]><p>{"Outlook"}</p><![endif]--></div> };
+    html! { <div><!--[if lt IE -9]><p>{"Old"}</p><![endif]--></div> };
+    html! { <div><!--[if mso]><p>{"Outlook"}</p><![end]--></div> };
+    html! { <p>{"Outlook"}</p><![endif]--> };
+}
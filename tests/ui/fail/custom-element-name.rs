@@ -0,0 +1,6 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <my-widget>{"Hello"}</my-widgit> };
+    html! { <My-Widget /> };
+}
@@ -0,0 +1,8 @@
+use tide_jsx::{html, rsx, Render};
+
+fn main() {
+    let page = rsx! { <ul><li>{"a"}</ul> };
+    let _ = page.render();
+    let html = html! { <div clas="x" /> };
+    let _ = html.len();
+}
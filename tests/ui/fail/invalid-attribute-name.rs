@@ -0,0 +1,7 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <div "a b onclick"="x" /> };
+    html! { <div "title>"={"x"} /> };
+    html! { <div ""="x" /> };
+}
@@ -0,0 +1,5 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <div title='x' /> };
+}
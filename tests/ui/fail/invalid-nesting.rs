@@ -0,0 +1,7 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <p><div>{"block"}</div></p> };
+    html! { <div><td>{"cell"}</td></div> };
+    html! { <a href={"/"}><span><a href={"/nested"}>{"link"}</a></span></a> };
+}
@@ -0,0 +1,10 @@
+use tide_jsx::html;
+
+fn main() {
+    html! {
+        <p>
+            @let total;
+            {"Total"}
+        </p>
+    };
+}
@@ -0,0 +1,7 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <div clas={"card"} /> };
+    html! { <a hreff={"/home"}>{"Home"}</a> };
+    html! { <span colspan={"2"} /> };
+}
@@ -0,0 +1,14 @@
+use tide_jsx::html;
+
+fn main() {
+    html! {
+      <div>
+        <ul>
+          <li>{"one"}
+          <li>{"two"}</li>
+        </ul>
+        <p>{"typo"}</pp>
+        <section>
+      </div>
+    };
+}
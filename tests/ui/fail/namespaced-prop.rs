@@ -0,0 +1,11 @@
+use tide_jsx::{component, html, rsx};
+
+#[component]
+fn Heading<'title>(title: &'title str) {
+    rsx! { <h1>{title}</h1> }
+}
+
+fn main() {
+    html! { <Heading title={"Hello"} @click={"open = true"} /> };
+    html! { <div xml:lang /> };
+}
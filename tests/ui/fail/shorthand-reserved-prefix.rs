@@ -0,0 +1,6 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <div.card.shadow#main>{"Hi"}</div> };
+    html! { <section#intro /> };
+}
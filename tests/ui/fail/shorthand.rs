@@ -0,0 +1,12 @@
+use tide_jsx::{component, html, rsx};
+
+#[component]
+fn Card<'a>(title: &'a str) {
+    rsx! { <div>{title}</div> }
+}
+
+fn main() {
+    html! { <Card.wide title={"Hi"} /> };
+    html! { <div #main #content.card /> };
+    html! { <div #main id="content" /> };
+}
@@ -0,0 +1,7 @@
+use tide_jsx::html;
+
+fn main() {
+    let extra = vec![("title", "Hello")];
+    html! { <div {extra} /> };
+    html! { <div title="a" {..extra} title="b" /> };
+}
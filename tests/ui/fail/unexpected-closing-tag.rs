@@ -0,0 +1,5 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <dt>{"Term"}</dt><dd>{"Details"}</dd></dl> };
+}
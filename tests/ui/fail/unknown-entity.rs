@@ -0,0 +1,6 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <p>{"Loading"}&helip;</p> };
+    html! { <p>&copy;&#xD800;</p> };
+}
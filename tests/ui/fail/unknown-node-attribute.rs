@@ -0,0 +1,10 @@
+use tide_jsx::html;
+
+fn main() {
+    html! {
+        <div>
+            #[whitespace(collapse)]
+            <code>"a  b"</code>
+        </div>
+    };
+}
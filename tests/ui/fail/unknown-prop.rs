@@ -0,0 +1,10 @@
+use tide_jsx::{component, html, rsx};
+
+#[component]
+fn Heading<'title>(title: &'title str) {
+    rsx! { <h1>{title}</h1> }
+}
+
+fn main() {
+    html! { <div><Heading titel={"Hello"}></Heading></div> };
+}
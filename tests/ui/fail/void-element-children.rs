@@ -0,0 +1,6 @@
+use tide_jsx::html;
+
+fn main() {
+    html! { <p>{"one"}<br>{"x"}</br>{"two"}</p> };
+    html! { <div><input type={"text"} aria-label={"Name"}></input></div> };
+}